@@ -0,0 +1,60 @@
+use std::fs::File;
+
+use tauri::AppHandle;
+use tauri_plugin_dialog::DialogExt;
+
+use crate::{wing::WingNodeInfo, AppData, MutableState};
+
+#[tauri::command]
+#[specta::specta]
+pub async fn browse_console(
+    state: MutableState<'_, AppData>,
+    path: String,
+    depth: u32,
+) -> Result<WingNodeInfo, String> {
+    let Some(console) = state.read().await.console.clone() else {
+        return Err("Console not connected".to_string());
+    };
+
+    console
+        .browse(&path, depth)
+        .await
+        .map_err(|err| format!("Failed to browse console: {}", err))
+}
+
+/// Walks the whole tree below `path` and writes it to a JSON file picked by the user.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_console_tree(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    path: String,
+) -> Result<(), String> {
+    let Some(console) = state.read().await.console.clone() else {
+        return Err("Console not connected".to_string());
+    };
+
+    let tree = console
+        .browse(&path, u32::MAX)
+        .await
+        .map_err(|err| format!("Failed to browse console: {}", err))?;
+
+    let export_file_path = tauri::async_runtime::spawn_blocking(move || {
+        handle
+            .dialog()
+            .file()
+            .add_filter("JSON", &["json"])
+            .blocking_save_file()
+    })
+    .await
+    .map_err(|err| format!("Failed to save file: {}", err))?
+    .ok_or_else(|| "Failed to save file".to_string())?
+    .into_path()
+    .map_err(|err| format!("Failed to convert to path: {}", err))?;
+
+    let file =
+        File::create(&export_file_path).map_err(|err| format!("Failed to open file: {}", err))?;
+
+    serde_json::to_writer_pretty(&file, &tree)
+        .map_err(|err| format!("Failed to write console tree: {}", err))
+}
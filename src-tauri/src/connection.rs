@@ -0,0 +1,291 @@
+use std::{sync::Arc, time::Duration};
+
+use libwing::WingConsole;
+use tauri::{async_runtime::JoinHandle, AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::{
+    cue::CueId,
+    monitor,
+    preferences::ConsoleBackendKind,
+    wing::{MixerBackend, SimulatedWing, Wing, WingDiscoveryInfo, WingRequestPolicy},
+    AppData, MutableState,
+};
+
+pub const DEFAULT_CONSOLE_ADDRESS: &str = "192.168.2.193";
+
+/// Interval in which a connected console is pinged to check the link.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+const RECONNECT_BACKOFF_MIN: Duration = Duration::from_millis(500);
+const RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(10);
+
+/// How long to wait for consoles to answer the discovery probe.
+const DISCOVERY_TIMEOUT: Duration = Duration::from_millis(1500);
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum ConsoleStatus {
+    #[default]
+    Disconnected,
+    Connecting(String),
+    Connected(String),
+    Simulated,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize, specta::Type, tauri_specta::Event)]
+pub enum ConsoleStatusEvent {
+    Connecting(String),
+    Connected(String),
+    /// The link to the console was lost; a reconnect is attempted automatically.
+    Lost(String),
+    /// The console is back after a lost link. Carries the cue that was active at that time,
+    /// so that the UI can offer to re-apply it.
+    Reconnected(String, Option<CueId>),
+    Disconnected,
+    Simulated,
+}
+
+/// Keeps the console connection alive for as long as it is wanted.
+#[derive(Default)]
+pub struct ConsoleConnection {
+    status: ConsoleStatus,
+    supervisor: Option<JoinHandle<()>>,
+}
+
+impl ConsoleConnection {
+    pub fn status(&self) -> &ConsoleStatus {
+        &self.status
+    }
+
+    /// Starts (or restarts) supervising a connection to the console at `address`.
+    pub fn start(&mut self, handle: &AppHandle, address: String) {
+        self.stop();
+        self.supervisor = Some(tauri::async_runtime::spawn(supervise(
+            handle.clone(),
+            address,
+        )));
+    }
+
+    /// Stops supervising any real console and hands out a fresh simulated one.
+    pub fn start_simulated(&mut self) -> Arc<dyn MixerBackend> {
+        self.stop();
+        self.status = ConsoleStatus::Simulated;
+        Arc::new(SimulatedWing::default())
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(supervisor) = self.supervisor.take() {
+            supervisor.abort();
+        }
+        self.status = ConsoleStatus::Disconnected;
+    }
+}
+
+async fn set_status(handle: &AppHandle, status: ConsoleStatus, event: ConsoleStatusEvent) {
+    let app_data: MutableState<'_, AppData> = handle.state();
+    app_data.write().await.connection.status = status;
+
+    let _ = event
+        .emit(handle)
+        .inspect_err(|err| log::error!("Failed to emit console status event: {}", err));
+}
+
+async fn connect(address: &str, request_policy: WingRequestPolicy) -> Result<Wing, String> {
+    let address = address.to_string();
+    tauri::async_runtime::spawn_blocking(move || WingConsole::connect(Some(&address)))
+        .await
+        .map_err(|err| err.to_string())?
+        .map_err(|err| err.to_string())
+        .and_then(|console| Wing::new(console, request_policy).map_err(|err| err.to_string()))
+}
+
+async fn supervise(handle: AppHandle, address: String) {
+    let mut was_connected = false;
+    let mut backoff = RECONNECT_BACKOFF_MIN;
+
+    loop {
+        set_status(
+            &handle,
+            ConsoleStatus::Connecting(address.clone()),
+            ConsoleStatusEvent::Connecting(address.clone()),
+        )
+        .await;
+
+        let request_policy = {
+            let app_data: MutableState<'_, AppData> = handle.state();
+            let app_data = app_data.read().await;
+            app_data.preferences.request_policy()
+        };
+
+        let wing: Arc<dyn MixerBackend> = match connect(&address, request_policy).await {
+            Ok(wing) => Arc::new(wing),
+            Err(err) => {
+                log::warn!("Failed to connect to Wing Console at {}: {}", address, err);
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(RECONNECT_BACKOFF_MAX);
+                continue;
+            }
+        };
+
+        backoff = RECONNECT_BACKOFF_MIN;
+
+        let current_cue_id = {
+            let app_data: MutableState<'_, AppData> = handle.state();
+            let mut app_data = app_data.write().await;
+            if app_data.network_monitor {
+                wing.set_traffic_listener(Some(monitor::traffic_listener(&handle)));
+            }
+            if let Some(old) = app_data.console.replace(wing.clone()) {
+                old.close();
+            }
+            if let Err(err) = app_data
+                .show
+                .mix_config
+                .apply_gain_offsets(wing.as_ref())
+                .await
+            {
+                log::error!("Failed to apply gain offsets: {}", err);
+            }
+            app_data.show_state.current_cue_id
+        };
+
+        log::info!("Wing connected at {}", address);
+        let event = if was_connected {
+            ConsoleStatusEvent::Reconnected(address.clone(), current_cue_id)
+        } else {
+            ConsoleStatusEvent::Connected(address.clone())
+        };
+        set_status(&handle, ConsoleStatus::Connected(address.clone()), event).await;
+        was_connected = true;
+
+        let err = loop {
+            tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+            if let Err(err) = wing.ping().await {
+                break err;
+            }
+        };
+
+        log::warn!("Lost connection to Wing Console: {}", err);
+        {
+            let app_data: MutableState<'_, AppData> = handle.state();
+            let mut app_data = app_data.write().await;
+            app_data.metering.stop();
+            wing.close();
+            app_data.console = None;
+        }
+
+        set_status(
+            &handle,
+            ConsoleStatus::Connecting(address.clone()),
+            ConsoleStatusEvent::Lost(err.to_string()),
+        )
+        .await;
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_console_status(state: MutableState<'_, AppData>) -> Result<ConsoleStatus, String> {
+    Ok(state.read().await.connection.status().clone())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn connect_console(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    address: String,
+) -> Result<(), String> {
+    let mut app_data = state.write().await;
+
+    app_data.metering.stop();
+    if let Some(console) = app_data.console.take() {
+        console.close();
+    }
+    app_data.connection.start(&handle, address.clone());
+
+    app_data.preferences.console_address = Some(address);
+    app_data.preferences.console_backend = ConsoleBackendKind::Wing;
+    app_data.preferences.save(&handle)?;
+
+    Ok(())
+}
+
+/// Replaces the console with an in-memory simulation, e.g. to program a show without a desk.
+#[tauri::command]
+#[specta::specta]
+pub async fn connect_simulated_console(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+) -> Result<(), String> {
+    let mut app_data = state.write().await;
+
+    app_data.metering.stop();
+    if let Some(console) = app_data.console.take() {
+        console.close();
+    }
+    let console = app_data.connection.start_simulated();
+    if app_data.network_monitor {
+        console.set_traffic_listener(Some(monitor::traffic_listener(&handle)));
+    }
+    app_data.console = Some(console);
+
+    app_data.preferences.console_backend = ConsoleBackendKind::Simulated;
+    app_data.preferences.save(&handle)?;
+
+    let _ = ConsoleStatusEvent::Simulated
+        .emit(&handle)
+        .inspect_err(|err| log::error!("Failed to emit console status event: {}", err));
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn disconnect_console(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+) -> Result<(), String> {
+    let mut app_data = state.write().await;
+
+    app_data.metering.stop();
+    app_data.connection.stop();
+    if let Some(console) = app_data.console.take() {
+        console.close();
+    }
+
+    let _ = ConsoleStatusEvent::Disconnected
+        .emit(&handle)
+        .inspect_err(|err| log::error!("Failed to emit console status event: {}", err));
+
+    Ok(())
+}
+
+/// Sets how long console requests wait for a reply and how often they are retried. The policy
+/// is used from the next connection on, a running connection keeps its policy.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_request_policy(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    timeout_ms: u32,
+    retries: u32,
+) -> Result<(), String> {
+    if timeout_ms == 0 {
+        return Err("Request timeout must be at least 1 ms".to_string());
+    }
+
+    let mut app_data = state.write().await;
+    app_data.preferences.request_timeout_ms = Some(timeout_ms);
+    app_data.preferences.request_retries = Some(retries);
+    app_data.preferences.save(&handle)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn discover_consoles() -> Result<Vec<WingDiscoveryInfo>, String> {
+    tauri::async_runtime::spawn_blocking(|| crate::wing::discover_consoles(DISCOVERY_TIMEOUT))
+        .await
+        .map_err(|err| format!("Failed to discover consoles: {}", err))?
+        .map_err(|err| format!("Failed to discover consoles: {}", err))
+}
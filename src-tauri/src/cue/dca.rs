@@ -1,16 +1,17 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::{
     cue::CueExecutionContext,
     mix::{error::MixError, ActorId, GroupId},
-    wing::{WingChannelId, WingColor, WingDcaId},
+    utils::Decibel,
+    wing::{MixerBackend, WingColor, WingDcaId, WingStripId},
 };
 
 const DEFAULT_DCA_COLOR: WingColor = WingColor::Red;
 
 #[derive(Default)]
 struct SingleDcaAssignmentBake {
-    channels: HashSet<WingChannelId>,
+    channels: HashSet<WingStripId>,
     color: Option<WingColor>,
     name: String,
 }
@@ -62,38 +63,142 @@ impl SingleDcaAssignment {
 #[serde(rename_all = "camelCase")]
 pub struct DcaAssignment {
     pub(super) assignment: [SingleDcaAssignment; 16],
+    /// Fader level of each DCA, `None` leaves the fader where it is
+    #[serde(default)]
+    pub(super) levels: [Option<Decibel>; 16],
+    /// Mute state of each DCA, `None` leaves the mute where it is
+    #[serde(default)]
+    pub(super) mutes: [Option<bool>; 16],
+}
+
+/// A DCA fader moving from its current position to the level stored in a cue.
+pub struct DcaLevelFade {
+    dca_id: WingDcaId,
+    /// Levels the fader moves between, linearly in dB
+    from: Decibel,
+    to: Decibel,
+}
+
+impl DcaLevelFade {
+    /// Sets the fader to the level at `t` (0.0..=1.0) of the fade.
+    pub fn apply(&self, wing: &dyn MixerBackend, t: f32) -> Result<(), MixError> {
+        let (from, to) = (self.from.as_f32(), self.to.as_f32());
+        let level = Decibel::new(from + (to - from) * t.clamp(0.0, 1.0));
+        wing.dca(self.dca_id).set_fader_level(level)?;
+        Ok(())
+    }
 }
 
 impl DcaAssignment {
+    pub fn uses_actor(&self, actor_id: ActorId) -> bool {
+        self.assignment
+            .iter()
+            .any(|a| matches!(a, SingleDcaAssignment::Actor(id) if *id == actor_id))
+    }
+
+    pub fn uses_group(&self, group_id: GroupId) -> bool {
+        self.assignment
+            .iter()
+            .any(|a| matches!(a, SingleDcaAssignment::Group(id) if *id == group_id))
+    }
+
+    /// Clears every DCA the group is assigned to. Returns whether anything changed.
+    pub fn remove_group(&mut self, group_id: GroupId) -> bool {
+        let mut changed = false;
+        for a in self.assignment.iter_mut() {
+            if matches!(a, SingleDcaAssignment::Group(id) if *id == group_id) {
+                *a = SingleDcaAssignment::None;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Clears every DCA the actor is assigned to. Returns whether anything changed.
+    pub fn remove_actor(&mut self, actor_id: ActorId) -> bool {
+        let mut changed = false;
+        for a in self.assignment.iter_mut() {
+            if matches!(a, SingleDcaAssignment::Actor(id) if *id == actor_id) {
+                *a = SingleDcaAssignment::None;
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    fn dca_ids() -> impl Iterator<Item = (usize, WingDcaId)> {
+        (0..16).map(|dca_idx| (dca_idx, ((dca_idx + 1) as u8).try_into().unwrap()))
+    }
+
+    /// Sets all stored DCA levels at once.
+    pub fn apply_levels(&self, context: &CueExecutionContext) -> Result<(), MixError> {
+        for (dca_idx, dca_id) in Self::dca_ids() {
+            if let Some(level) = self.levels[dca_idx] {
+                context.wing.dca(dca_id).set_fader_level(level)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Prepares fades from the current DCA levels to the stored ones.
+    pub async fn level_fades<'a>(
+        &self,
+        context: &CueExecutionContext<'a>,
+    ) -> Result<Vec<DcaLevelFade>, MixError> {
+        let mut fades = Vec::new();
+        for (dca_idx, dca_id) in Self::dca_ids() {
+            let Some(level) = self.levels[dca_idx] else {
+                continue;
+            };
+
+            let current = context.wing.dca(dca_id).get_fader_level().await?;
+            fades.push(DcaLevelFade {
+                dca_id,
+                from: current,
+                to: level,
+            });
+        }
+
+        Ok(fades)
+    }
+
     pub async fn apply<'a>(&self, context: &CueExecutionContext<'a>) -> Result<(), MixError> {
         let assignments = self.assignment.map(|a| a.bake_assignment(context));
 
-        // Mute and unassign unused channels
-        // TODO: find a cleaner way to do this
-        for channel_id in context
+        let mut channel_dcas: HashMap<WingStripId, Vec<WingDcaId>> = context
             .config
             .controlled_channels()
-            .filter(|channel_id| !assignments.iter().any(|a| a.channels.contains(channel_id)))
-        {
-            let channel = context.wing.channel(*channel_id);
-            channel.mute()?;
-            channel.set_dcas([]).await?;
-        }
+            .map(|channel_id| (*channel_id, Vec::new()))
+            .collect();
 
-        // Unmute and assign current channels
         for (dca_idx, assignment) in assignments.into_iter().enumerate() {
             let dca_id: WingDcaId = ((dca_idx + 1) as u8).try_into().unwrap();
 
             let dca = context.wing.dca(dca_id);
             dca.set_name(&assignment.name)?;
             dca.set_color(assignment.color.unwrap_or(DEFAULT_DCA_COLOR))?;
+            match self.mutes[dca_idx] {
+                Some(true) => dca.mute()?,
+                Some(false) => dca.unmute()?,
+                None => {}
+            }
+
+            for channel_id in assignment.channels {
+                channel_dcas.entry(channel_id).or_default().push(dca_id);
+            }
+        }
 
-            for channel in assignment.channels.into_iter() {
-                let channel = context.wing.channel(channel);
+        // Unused channels are muted and unassigned, assigned channels are unmuted
+        for (channel_id, dcas) in channel_dcas {
+            let channel = context.wing.channel(channel_id);
 
+            if dcas.is_empty() {
+                channel.mute()?;
+            } else {
                 channel.unmute()?;
-                channel.set_dcas([dca_id]).await?;
             }
+            channel.set_dcas(dcas).await?;
         }
 
         Ok(())
@@ -0,0 +1,99 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    cue::CueExecutionContext,
+    mix::error::MixError,
+    wing::{WingFxParameter, WingFxSlotId},
+};
+
+/// The state a cue puts an FX slot in. Everything that is not set is left as it is.
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FxSlotState {
+    engaged: Option<bool>,
+    parameters: BTreeMap<WingFxParameter, f32>,
+}
+
+impl FxSlotState {
+    pub fn apply(
+        &self,
+        slot_id: WingFxSlotId,
+        context: &CueExecutionContext,
+    ) -> Result<(), MixError> {
+        let slot = context.wing.fx_slot(slot_id);
+
+        if let Some(engaged) = self.engaged {
+            slot.set_engaged(engaged)?;
+        }
+        for (parameter, value) in &self.parameters {
+            slot.set_parameter(*parameter, *value)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct FxAssignment {
+    assignment: HashMap<WingFxSlotId, FxSlotState>,
+}
+
+impl FxAssignment {
+    pub fn apply(&self, context: &CueExecutionContext) -> Result<(), MixError> {
+        for (slot_id, state) in &self.assignment {
+            state.apply(*slot_id, context)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn set(&mut self, slot_id: WingFxSlotId, state: Option<FxSlotState>) {
+        match state {
+            Some(state) => {
+                self.assignment.insert(slot_id, state);
+            }
+            None => {
+                self.assignment.remove(&slot_id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libwing::WingConsole;
+
+    use super::*;
+    use crate::{
+        mix::MixConfig,
+        wing::{id::WingId, MixerBackend, SimulatedWing, WingValue},
+    };
+
+    #[test]
+    fn apply_leaves_unset_slots_alone() {
+        let wing = SimulatedWing::default();
+        let config = MixConfig::default();
+        let context = CueExecutionContext {
+            config: &config,
+            wing: &wing as &dyn MixerBackend,
+        };
+
+        let mut assignment = FxAssignment::default();
+        assignment.set(
+            WingFxSlotId::new(1).unwrap(),
+            Some(FxSlotState {
+                engaged: Some(true),
+                parameters: BTreeMap::from([(WingFxParameter::Decay, 2.5)]),
+            }),
+        );
+        assignment.apply(&context).unwrap();
+
+        assert_eq!(wing.value_at("/fx/1/on"), Some(WingValue::Int(1)));
+        assert_eq!(wing.value_at("/fx/1/decay"), Some(WingValue::Float(2.5)));
+        for path in ["/fx/1/pdel", "/fx/2/on", "/fx/2/decay"] {
+            assert!(WingConsole::name_to_id(path).is_some());
+            assert_eq!(wing.value_at(path), None, "{} was written", path);
+        }
+    }
+}
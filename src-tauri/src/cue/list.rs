@@ -27,6 +27,10 @@ impl CueList {
         self.0.iter()
     }
 
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Cue> {
+        self.0.iter_mut()
+    }
+
     pub fn has(&self, cue_id: &CueId) -> bool {
         self.0.iter().any(|c| &c.id == cue_id)
     }
@@ -1,7 +1,9 @@
+use std::time::{Duration, Instant};
+
 use crate::{
-    mix::{error::MixError, MixConfig},
-    utils::ClampedValue,
-    wing::Wing,
+    mix::{error::MixError, ActorId, GroupId, MixConfig, PositionId},
+    utils::{ClampedValue, Decibel},
+    wing::{MixerBackend, WingFxSlotId},
 };
 
 mod dca;
@@ -10,13 +12,22 @@ pub use dca::*;
 mod position;
 pub use position::*;
 
+mod mute_group;
+pub use mute_group::*;
+
+mod fx;
+pub use fx::*;
+
 mod list;
 pub use list::*;
 
+/// Interval in which fading parameters are updated during a fade.
+const FADE_STEP_INTERVAL: Duration = Duration::from_millis(40);
+
 #[derive(Clone)]
 pub struct CueExecutionContext<'a> {
     pub config: &'a MixConfig,
-    pub wing: &'a Wing,
+    pub wing: &'a dyn MixerBackend,
 }
 
 #[derive(
@@ -60,6 +71,12 @@ pub struct Cue {
     dca: DcaAssignment,
 
     position: PositionAssignment,
+
+    #[serde(default)]
+    mute_groups: MuteGroupAssignment,
+
+    #[serde(default)]
+    fx: FxAssignment,
 }
 
 impl Cue {
@@ -71,29 +88,112 @@ impl Cue {
             snap: 0.0.into(),
             dca: DcaAssignment::default(),
             position: PositionAssignment::default(),
+            mute_groups: MuteGroupAssignment::default(),
+            fx: FxAssignment::default(),
         }
     }
 
     pub async fn activate<'a>(&self, context: CueExecutionContext<'a>) -> Result<(), MixError> {
-        if self.fade_time > 0.0 {
-            // TODO: in fade
+        if self.fade_time <= 0.0 {
+            return self.snap(&context, true).await;
         }
 
-        self.snap(&context).await?;
+        let fades = self.dca.level_fades(&context).await?;
+
+        let fade_time = Duration::from_secs_f32(self.fade_time);
+        let snap_at = fade_time.mul_f32(self.snap.as_f32());
+        let start = Instant::now();
+        let mut snapped = false;
+
+        loop {
+            let elapsed = start.elapsed();
+            if !snapped && elapsed >= snap_at {
+                self.snap(&context, false).await?;
+                snapped = true;
+            }
+
+            let t = (elapsed.as_secs_f32() / self.fade_time).min(1.0);
+            let transaction = context.wing.transaction();
+            for fade in &fades {
+                fade.apply(&transaction, t)?;
+            }
+            transaction.commit()?;
+
+            if t >= 1.0 {
+                break;
+            }
+            tokio::time::sleep(FADE_STEP_INTERVAL).await;
+        }
 
         Ok(())
     }
 
-    async fn snap<'a>(&self, context: &CueExecutionContext<'a>) -> Result<(), MixError> {
-        self.dca.apply(context).await?;
-        self.position.apply(context)?;
+    /// Applies all non-fade parameters. Levels are only included if they are not faded.
+    async fn snap<'a>(
+        &self,
+        context: &CueExecutionContext<'a>,
+        include_levels: bool,
+    ) -> Result<(), MixError> {
+        // Stage everything first, so that only actual changes are sent in a single burst
+        let transaction = context.wing.transaction();
+        let snap_context = CueExecutionContext {
+            config: context.config,
+            wing: &transaction,
+        };
+
+        self.dca.apply(&snap_context).await?;
+        self.position.apply(&snap_context)?;
+        self.mute_groups.apply(&snap_context)?;
+        self.fx.apply(&snap_context)?;
+        if include_levels {
+            self.dca.apply_levels(&snap_context)?;
+        }
+
+        transaction.commit()?;
         Ok(())
     }
 
+    pub fn uses_actor(&self, actor_id: ActorId) -> bool {
+        self.dca.uses_actor(actor_id) || self.position.uses_actor(actor_id)
+    }
+
+    /// Removes every reference to the actor. Returns whether the cue changed.
+    pub fn remove_actor(&mut self, actor_id: ActorId) -> bool {
+        let dca_changed = self.dca.remove_actor(actor_id);
+        let position_changed = self.position.remove_actor(actor_id);
+        dca_changed || position_changed
+    }
+
+    pub fn uses_group(&self, group_id: GroupId) -> bool {
+        self.dca.uses_group(group_id)
+    }
+
+    /// Removes every reference to the group. Returns whether the cue changed.
+    pub fn remove_group(&mut self, group_id: GroupId) -> bool {
+        self.dca.remove_group(group_id)
+    }
+
+    pub fn uses_position(&self, position_id: PositionId) -> bool {
+        self.position.uses_position(position_id)
+    }
+
+    /// Removes every reference to the position. Returns whether the cue changed.
+    pub fn remove_position(&mut self, position_id: PositionId) -> bool {
+        self.position.remove_position(position_id)
+    }
+
+    pub fn set_position_assignment(&mut self, actor_id: ActorId, position_id: Option<PositionId>) {
+        self.position.set(actor_id, position_id);
+    }
+
     pub fn rename(&mut self, name: String) {
         self.name = name;
     }
 
+    pub fn set_fade_time(&mut self, fade_time: f32) {
+        self.fade_time = fade_time.max(0.0);
+    }
+
     pub fn set_assignment(
         &mut self,
         dca_index: u8,
@@ -107,4 +207,178 @@ impl Cue {
         *current_assignment = assignment;
         Ok(())
     }
+
+    pub fn set_dca_level(&mut self, dca_index: u8, level: Option<Decibel>) -> Result<(), MixError> {
+        let current_level = self
+            .dca
+            .levels
+            .get_mut(dca_index as usize)
+            .ok_or_else(|| MixError::DcaIndexOutOfRange(dca_index))?;
+        *current_level = level;
+        Ok(())
+    }
+
+    pub fn set_mute_group_state(
+        &mut self,
+        mute_group_index: u8,
+        state: Option<bool>,
+    ) -> Result<(), MixError> {
+        let current_state = self
+            .mute_groups
+            .states
+            .get_mut(mute_group_index as usize)
+            .ok_or_else(|| MixError::MuteGroupIndexOutOfRange(mute_group_index))?;
+        *current_state = state;
+        Ok(())
+    }
+
+    pub fn set_fx_slot_state(&mut self, slot_id: WingFxSlotId, state: Option<FxSlotState>) {
+        self.fx.set(slot_id, state);
+    }
+
+    pub fn set_dca_mute(&mut self, dca_index: u8, mute: Option<bool>) -> Result<(), MixError> {
+        let current_mute = self
+            .dca
+            .mutes
+            .get_mut(dca_index as usize)
+            .ok_or_else(|| MixError::DcaIndexOutOfRange(dca_index))?;
+        *current_mute = mute;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libwing::WingConsole;
+
+    use super::*;
+    use crate::{
+        mix::Position,
+        wing::{SimulatedWing, WingStripId, WingStripKind, WingValue},
+    };
+
+    fn channel(number: u8) -> WingStripId {
+        WingStripId::new(WingStripKind::Channel, number).unwrap()
+    }
+
+    fn string(value: &str) -> Option<WingValue> {
+        Some(WingValue::String(value.to_string()))
+    }
+
+    /// Alice on channel 1 is on DCA 1 at a hard left position, Bob on channel 2 is unused.
+    fn setup() -> (MixConfig, Cue) {
+        let mut config = MixConfig::default();
+        let (alice, _) = config.add_actor(channel(1), "Alice".to_string(), None);
+        config.add_actor(channel(2), "Bob".to_string(), None);
+
+        let (hard_left, _) = config.add_position("Hard left".to_string());
+        let position: Position =
+            serde_json::from_value(serde_json::json!({ "name": "Hard left", "pan": 0.0 })).unwrap();
+        config.update_position(hard_left, position).unwrap();
+
+        let mut cue = Cue::new(CueId::new(1, 0), "Scene 1".to_string());
+        cue.set_assignment(0, SingleDcaAssignment::Actor(alice))
+            .unwrap();
+        cue.set_dca_mute(1, Some(true)).unwrap();
+        cue.set_position_assignment(alice, Some(hard_left));
+
+        (config, cue)
+    }
+
+    fn activate(wing: &SimulatedWing, config: &MixConfig, cue: &Cue) {
+        let context = CueExecutionContext {
+            config,
+            wing: wing as &dyn MixerBackend,
+        };
+        tauri::async_runtime::block_on(cue.activate(context)).unwrap();
+    }
+
+    #[test]
+    fn activate_assigns_actors_to_dcas() {
+        let (config, cue) = setup();
+        let wing = SimulatedWing::default();
+        activate(&wing, &config, &cue);
+
+        assert_eq!(wing.value_at("/ch/1/tags"), string("#D1"));
+        assert_eq!(wing.value_at("/ch/2/tags"), string(""));
+        assert_eq!(wing.value_at("/dca/1/name"), string("Alice"));
+        assert_eq!(wing.value_at("/dca/2/name"), string(""));
+    }
+
+    #[test]
+    fn activate_mutes_unused_channels_and_dcas() {
+        let (config, cue) = setup();
+        let wing = SimulatedWing::default();
+        activate(&wing, &config, &cue);
+
+        assert_eq!(wing.value_at("/ch/1/mute"), Some(WingValue::Int(0)));
+        assert_eq!(wing.value_at("/ch/2/mute"), Some(WingValue::Int(1)));
+        assert_eq!(wing.value_at("/dca/2/mute"), Some(WingValue::Int(1)));
+        assert_eq!(wing.value_at("/dca/1/mute"), None);
+    }
+
+    #[test]
+    fn activate_pans_actors_to_their_positions() {
+        let (config, cue) = setup();
+        let wing = SimulatedWing::default();
+        activate(&wing, &config, &cue);
+
+        assert_eq!(wing.value_at("/ch/1/pan"), Some(WingValue::Int(-100)));
+        assert_eq!(wing.value_at("/ch/2/pan"), Some(WingValue::Int(0)));
+    }
+
+    #[test]
+    fn activate_resets_delay_and_sends_of_the_previous_position() {
+        let (mut config, mut cue) = setup();
+        let stage = serde_json::from_value(serde_json::json!({
+            "width": 10.0,
+            "depth": 8.0,
+            "speakers": [
+                { "name": "Main L", "location": { "x": -1.0, "y": 0.0 }, "send": null },
+                { "name": "Main R", "location": { "x": 11.0, "y": 0.0 }, "send": null },
+                { "name": "Fill", "location": { "x": 2.0, "y": -1.0 }, "send": { "Matrix": 1 } },
+            ],
+        }))
+        .unwrap();
+        config.set_stage(stage);
+
+        let alice = config.actors().map(|(id, _)| *id).min().unwrap();
+        let (front, _) = config.add_position("Front".to_string());
+        let position: Position = serde_json::from_value(
+            serde_json::json!({ "name": "Front", "location": { "x": 2.0, "y": 1.0 } }),
+        )
+        .unwrap();
+        config.update_position(front, position).unwrap();
+
+        cue.set_position_assignment(alice, Some(front));
+        let wing = SimulatedWing::default();
+
+        activate(&wing, &config, &cue);
+        assert_eq!(
+            wing.value_at("/ch/1/send/MX1/lvl"),
+            Some(WingValue::Float(0.0))
+        );
+        assert!(wing.value_at("/ch/1/dly/dly").unwrap().as_float() > 0.0);
+
+        cue.set_position_assignment(alice, None);
+        activate(&wing, &config, &cue);
+        assert_eq!(
+            wing.value_at("/ch/1/send/MX1/lvl"),
+            Some(WingValue::Float(Decibel::MIN))
+        );
+        assert_eq!(wing.value_at("/ch/1/dly/dly"), Some(WingValue::Float(0.0)));
+    }
+
+    #[test]
+    fn activate_keeps_existing_tags() {
+        let (config, cue) = setup();
+        let wing = SimulatedWing::default();
+        wing.set_value(
+            WingConsole::name_to_id("/ch/1/tags").unwrap(),
+            WingValue::String("#D4,#M2".to_string()),
+        );
+        activate(&wing, &config, &cue);
+
+        assert_eq!(wing.value_at("/ch/1/tags"), string("#D1,#M2"));
+    }
 }
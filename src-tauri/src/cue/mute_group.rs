@@ -0,0 +1,23 @@
+use crate::{cue::CueExecutionContext, mix::error::MixError, wing::WingMuteGroupId};
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct MuteGroupAssignment {
+    /// State of each mute group, `None` leaves the mute group as it is
+    pub(super) states: [Option<bool>; 8],
+}
+
+impl MuteGroupAssignment {
+    pub fn apply(&self, context: &CueExecutionContext) -> Result<(), MixError> {
+        for (idx, state) in self.states.iter().enumerate() {
+            let Some(muted) = state else {
+                continue;
+            };
+
+            let mute_group_id: WingMuteGroupId = ((idx + 1) as u8).try_into().unwrap();
+            context.wing.mute_group(mute_group_id).set_muted(*muted)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 use crate::{
     cue::CueExecutionContext,
@@ -12,14 +12,60 @@ pub struct PositionAssignment {
 }
 
 impl PositionAssignment {
+    pub fn uses_actor(&self, actor_id: ActorId) -> bool {
+        self.assignment.contains_key(&actor_id)
+    }
+
+    /// Assigns the actor to a position, `None` puts the actor back to the default position.
+    pub fn set(&mut self, actor_id: ActorId, position_id: Option<PositionId>) {
+        match position_id {
+            Some(position_id) => {
+                self.assignment.insert(actor_id, position_id);
+            }
+            None => {
+                self.assignment.remove(&actor_id);
+            }
+        }
+    }
+
+    pub fn uses_position(&self, position_id: PositionId) -> bool {
+        self.assignment.values().any(|id| *id == position_id)
+    }
+
+    /// Unassigns every actor from the position. Returns whether anything changed.
+    pub fn remove_position(&mut self, position_id: PositionId) -> bool {
+        let len = self.assignment.len();
+        self.assignment.retain(|_, id| *id != position_id);
+        self.assignment.len() != len
+    }
+
+    /// Returns whether the actor had a position assigned.
+    pub fn remove_actor(&mut self, actor_id: ActorId) -> bool {
+        self.assignment.remove(&actor_id).is_some()
+    }
+
     pub fn apply(&self, context: &CueExecutionContext) -> Result<(), MixError> {
+        // Every send a speaker or a position uses is controlled by the positions
+        let stage = context.config.stage();
+        let sends: BTreeSet<_> = stage
+            .speakers()
+            .iter()
+            .filter_map(|speaker| speaker.send)
+            .chain(
+                context
+                    .config
+                    .positions()
+                    .flat_map(|(_, position)| position.sends().iter().map(|send| send.target)),
+            )
+            .collect();
+
         for (actor_id, actor) in context.config.actors() {
             let position_id = self.assignment.get(actor_id);
             let position = position_id.and_then(|id| context.config.position(*id).ok());
             let position = position.cloned().unwrap_or_default();
 
             let channel = context.wing.channel(*actor.channel());
-            position.apply(&channel)?;
+            position.apply(&channel, stage, &sends)?;
         }
 
         Ok(())
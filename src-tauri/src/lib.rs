@@ -1,27 +1,56 @@
 use std::{path::PathBuf, sync::Arc};
 
-use libwing::WingConsole;
 use tauri::{
     async_runtime::RwLock,
     menu::{MenuBuilder, SubmenuBuilder},
-    Manager, State,
+    Manager, RunEvent, State,
 };
+use tauri_plugin_log::RotationStrategy;
+use tokio::task::AbortHandle;
 
 use crate::{
-    mix::{add_actor, add_group, get_wing_channel_info, import_actors, ActorEvent, GroupEvent},
+    browser::{browse_console, export_console_tree},
+    connection::{
+        connect_console, connect_simulated_console, disconnect_console, discover_consoles,
+        get_console_status, set_request_policy, ConsoleConnection, ConsoleStatusEvent,
+    },
+    meter::{set_meter_frame_rate, start_metering, stop_metering, MeterFrameEvent, Metering},
+    mix::{
+        add_actor, add_group, add_group_member, add_position, compare_channel_eq, delete_actor,
+        delete_group, delete_position, get_channel_dynamics, get_channel_eq, get_channel_gain,
+        get_channel_send, get_mute_group_states, get_wing_channel_info, import_actors,
+        preview_actor_sync, remove_group_member, set_actor_gain_offset, set_actor_mute_groups,
+        set_channel_dynamics, set_channel_eq, set_channel_input_gain, set_channel_send,
+        set_channel_trim, set_mute_group_state, set_stage, sync_actors_to_console, update_actor,
+        update_group, update_position, ActorEvent, GroupEvent, PositionEvent, StageEvent,
+    },
+    monitor::{set_network_monitor, ConsoleTrafficEvent},
+    preferences::{get_preferences, ConsoleBackendKind, Preferences},
     show::{
-        add_cue, delete_cue, get_show, goto_cue, new_show, open_show, rename_cue, save_show,
-        save_show_as, set_cue_dca_assignment, Show, ShowEvent, ShowState, ShowStateEvent,
+        add_cue, delete_cue, get_show, goto_cue, new_show, open_show, reapply_current_cue,
+        rename_cue, save_show, save_show_as, set_cue_dca_assignment, set_cue_dca_level,
+        set_cue_dca_mute, set_cue_fade_time, set_cue_fx_slot, set_cue_mute_group,
+        set_cue_position_assignment, Show, ShowEvent, ShowState, ShowStateEvent,
     },
-    wing::Wing,
+    wing::MixerBackend,
 };
 
+mod browser;
+mod connection;
 mod cue;
+mod meter;
 mod mix;
+mod monitor;
+mod preferences;
 mod show;
 mod utils;
 mod wing;
 
+/// Size in bytes after which the log file is rotated.
+const LOG_FILE_MAX_SIZE: u128 = 10_000_000;
+/// Number of rotated log files that are kept.
+const LOG_FILES_KEPT: usize = 5;
+
 pub type MutableState<'a, T> = State<'a, Arc<RwLock<T>>>;
 
 struct AppData {
@@ -29,37 +58,31 @@ struct AppData {
     current_show_file_path: Option<PathBuf>,
 
     show_state: ShowState,
+    /// The cue that is currently being applied (e.g. still fading)
+    running_cue: Option<AbortHandle>,
 
-    console: Option<Wing>,
+    console: Option<Arc<dyn MixerBackend>>,
+    connection: ConsoleConnection,
+    /// Whether all console traffic is mirrored to the UI
+    network_monitor: bool,
+    metering: Metering,
+
+    preferences: Preferences,
 }
 
 impl AppData {
-    fn new(allow_unconnected: bool) -> Result<Self, String> {
-        let wing_res = WingConsole::connect(Some("192.168.2.193"));
-
-        let wing = match wing_res {
-            Ok(wing) => {
-                println!("Wing connected");
-                Some(wing)
-            }
-            Err(err) => {
-                if allow_unconnected {
-                    println!("Failed to connect to Wing Console: {}", err);
-                    None
-                } else {
-                    return Err(format!("Failed to connect to Wing Console: {}", err));
-                }
-            }
-        };
-
-        let wing = wing.map(|wing| wing.into());
-
-        Ok(Self {
+    fn new(preferences: Preferences) -> Self {
+        Self {
             show: Show::default(),
             current_show_file_path: None,
             show_state: ShowState::default(),
-            console: wing,
-        })
+            running_cue: None,
+            console: None,
+            connection: ConsoleConnection::default(),
+            network_monitor: false,
+            metering: Metering::default(),
+            preferences,
+        }
     }
 }
 
@@ -70,20 +93,71 @@ pub fn run() {
         .commands(tauri_specta::collect_commands![
             get_show,
             get_wing_channel_info,
+            get_channel_eq,
+            set_channel_eq,
+            compare_channel_eq,
+            get_channel_dynamics,
+            set_channel_dynamics,
+            get_channel_send,
+            set_channel_send,
+            get_channel_gain,
+            set_channel_trim,
+            set_channel_input_gain,
+            set_actor_gain_offset,
+            preview_actor_sync,
+            sync_actors_to_console,
+            get_mute_group_states,
+            set_mute_group_state,
+            set_actor_mute_groups,
             add_actor,
             import_actors,
+            update_actor,
+            delete_actor,
             add_group,
+            update_group,
+            add_group_member,
+            remove_group_member,
+            delete_group,
+            add_position,
+            update_position,
+            delete_position,
+            set_stage,
             add_cue,
             delete_cue,
             rename_cue,
             set_cue_dca_assignment,
-            goto_cue
+            set_cue_position_assignment,
+            set_cue_dca_level,
+            set_cue_dca_mute,
+            set_cue_fade_time,
+            set_cue_mute_group,
+            set_cue_fx_slot,
+            goto_cue,
+            reapply_current_cue,
+            get_console_status,
+            connect_console,
+            connect_simulated_console,
+            disconnect_console,
+            discover_consoles,
+            set_request_policy,
+            get_preferences,
+            set_network_monitor,
+            start_metering,
+            stop_metering,
+            set_meter_frame_rate,
+            browse_console,
+            export_console_tree
         ])
         .events(tauri_specta::collect_events![
             ShowEvent,
             ShowStateEvent,
             ActorEvent,
-            GroupEvent
+            GroupEvent,
+            PositionEvent,
+            StageEvent,
+            ConsoleStatusEvent,
+            ConsoleTrafficEvent,
+            MeterFrameEvent
         ]);
 
     #[cfg(debug_assertions)] // <- Only export on non-release builds
@@ -95,15 +169,44 @@ pub fn run() {
         .expect("Failed to export typescript bindings");
 
     tauri::Builder::default()
+        .plugin(
+            tauri_plugin_log::Builder::new()
+                .level(if cfg!(debug_assertions) {
+                    log::LevelFilter::Debug
+                } else {
+                    log::LevelFilter::Info
+                })
+                .max_file_size(LOG_FILE_MAX_SIZE)
+                .rotation_strategy(RotationStrategy::KeepSome(LOG_FILES_KEPT))
+                .build(),
+        )
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_opener::init())
         .invoke_handler(builder.invoke_handler())
         .setup(move |app| {
             builder.mount_events(app);
 
-            let app_data = AppData::new(cfg!(debug_assertions)).unwrap();
+            let preferences = Preferences::load(app.handle()).unwrap_or_else(|err| {
+                log::warn!("Failed to load preferences: {}", err);
+                Preferences::default()
+            });
+            let console_backend = preferences.console_backend;
+            let console_address = preferences.console_address().to_string();
+
+            let app_data = Arc::new(RwLock::new(AppData::new(preferences)));
+            app.manage(app_data.clone());
 
-            app.manage(Arc::new(RwLock::new(app_data)));
+            {
+                let mut app_data = app_data.blocking_write();
+                match console_backend {
+                    ConsoleBackendKind::Wing => {
+                        app_data.connection.start(app.handle(), console_address)
+                    }
+                    ConsoleBackendKind::Simulated => {
+                        app_data.console = Some(app_data.connection.start_simulated())
+                    }
+                }
+            }
 
             let file_menu = SubmenuBuilder::new(app, "File")
                 .text("new", "New")
@@ -124,7 +227,7 @@ pub fn run() {
                     tauri::async_runtime::spawn(async move {
                         let _ = new_show(handle)
                             .await
-                            .inspect_err(|err| println!("Failed to load new show: {}", err));
+                            .inspect_err(|err| log::error!("Failed to load new show: {}", err));
                     });
                 }
                 "save" => {
@@ -132,7 +235,7 @@ pub fn run() {
                     tauri::async_runtime::spawn(async move {
                         let _ = save_show(handle)
                             .await
-                            .inspect_err(|err| println!("Failed to save: {}", err));
+                            .inspect_err(|err| log::error!("Failed to save: {}", err));
                     });
                 }
                 "save-as" => {
@@ -140,7 +243,7 @@ pub fn run() {
                     tauri::async_runtime::spawn(async move {
                         let _ = save_show_as(handle)
                             .await
-                            .inspect_err(|err| println!("Failed to save as: {}", err));
+                            .inspect_err(|err| log::error!("Failed to save as: {}", err));
                     });
                 }
                 "open" => {
@@ -148,7 +251,7 @@ pub fn run() {
                     tauri::async_runtime::spawn(async move {
                         let _ = open_show(handle)
                             .await
-                            .inspect_err(|err| println!("Failed to open: {}", err));
+                            .inspect_err(|err| log::error!("Failed to open: {}", err));
                     });
                 }
                 "quit" => handle.exit(0),
@@ -157,6 +260,14 @@ pub fn run() {
 
             Ok(())
         })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|handle, event| {
+            if let RunEvent::Exit = event {
+                let app_data: MutableState<'_, AppData> = handle.state();
+                if let Some(console) = app_data.blocking_read().console.as_ref() {
+                    console.close();
+                }
+            }
+        });
 }
@@ -0,0 +1,254 @@
+use itertools::Itertools;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tauri::{async_runtime::JoinHandle, AppHandle, Manager};
+use tauri_specta::Event;
+
+use crate::{
+    connection::ConsoleStatus,
+    mix::ActorId,
+    utils::Decibel,
+    wing::{
+        error::WingError, id::WingId, MeterSource, SimulatedMeterSource, WingChannelId, WingDcaId,
+        WingMeterSource, WingMeterStore, WingStripId, WingStripKind,
+    },
+    AppData, MutableState,
+};
+
+pub const DEFAULT_METER_FRAME_RATE: u32 = 20;
+/// Frames beyond this rate would only load the UI without being visible.
+pub const MAX_METER_FRAME_RATE: u32 = 60;
+
+/// How often the simulated meter source updates its levels.
+const SIMULATED_METER_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Meter levels of one frame. Only emitted while metering is running.
+#[derive(Clone, serde::Serialize, serde::Deserialize, specta::Type, tauri_specta::Event)]
+#[serde(rename_all = "camelCase")]
+pub struct MeterFrameEvent {
+    /// Level of each actor's channel. Only input channels are metered.
+    actors: HashMap<ActorId, Decibel>,
+    /// Actors on strips that are not metered (e.g. aux inputs), so the UI can tell them apart
+    /// from silent ones
+    unmetered_actors: Vec<ActorId>,
+    /// Post fader level of each DCA
+    dcas: Vec<Decibel>,
+}
+
+/// A running meter subscription: a thread filling the store and a task emitting frames.
+#[derive(Default)]
+pub struct Metering {
+    stop: Option<Arc<AtomicBool>>,
+    store: Option<Arc<WingMeterStore>>,
+    emitter: Option<JoinHandle<()>>,
+}
+
+impl Metering {
+    pub fn is_running(&self) -> bool {
+        self.stop
+            .as_ref()
+            .is_some_and(|stop| !stop.load(Ordering::Relaxed))
+    }
+
+    /// Starts (or restarts) metering from an opened `source`, emitting `frame_rate` frames per
+    /// second. If the source fails, metering stops.
+    pub fn start(&mut self, handle: &AppHandle, source: Box<dyn MeterSource>, frame_rate: u32) {
+        self.stop();
+
+        let store = Arc::new(WingMeterStore::default());
+        let stop = Arc::new(AtomicBool::new(false));
+
+        std::thread::spawn({
+            let store = store.clone();
+            let stop = stop.clone();
+            move || {
+                if let Err(err) = source.run(&store, &stop) {
+                    log::error!("Metering stopped: {}", err);
+                }
+                store.clear();
+                stop.store(true, Ordering::Relaxed);
+            }
+        });
+
+        self.stop = Some(stop);
+        self.store = Some(store);
+        self.set_frame_rate(handle, frame_rate);
+    }
+
+    /// Changes the rate of a running metering, without touching its source.
+    pub fn set_frame_rate(&mut self, handle: &AppHandle, frame_rate: u32) {
+        let (Some(stop), Some(store)) = (self.stop.clone(), self.store.clone()) else {
+            return;
+        };
+
+        if let Some(emitter) = self.emitter.take() {
+            emitter.abort();
+        }
+        self.emitter = Some(tauri::async_runtime::spawn(emit_frames(
+            handle.clone(),
+            store,
+            stop,
+            frame_rate,
+        )));
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            stop.store(true, Ordering::Relaxed);
+        }
+        self.store = None;
+        if let Some(emitter) = self.emitter.take() {
+            emitter.abort();
+        }
+    }
+}
+
+/// Emits frames until the source has stopped.
+async fn emit_frames(
+    handle: AppHandle,
+    store: Arc<WingMeterStore>,
+    stop: Arc<AtomicBool>,
+    frame_rate: u32,
+) {
+    let interval = Duration::from_secs_f32(1.0 / frame_rate.max(1) as f32);
+
+    loop {
+        tokio::time::sleep(interval).await;
+        if stop.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let mut actors = HashMap::new();
+        let mut unmetered_actors = Vec::new();
+        {
+            let app_data: MutableState<'_, AppData> = handle.state();
+            let app_data = app_data.read().await;
+            for (actor_id, actor) in app_data.show.mix_config.actors() {
+                match metered_channel(actor.channel()) {
+                    Some(channel_id) => {
+                        actors.insert(*actor_id, store.channel(channel_id));
+                    }
+                    None => unmetered_actors.push(*actor_id),
+                }
+            }
+        }
+
+        let dcas = (WingDcaId::MIN_ID..=WingDcaId::MAX_ID)
+            .map(|dca_idx| store.dca(WingDcaId::new(dca_idx).unwrap()))
+            .collect();
+
+        let _ = MeterFrameEvent {
+            actors,
+            unmetered_actors,
+            dcas,
+        }
+        .emit(&handle)
+        .inspect_err(|err| log::error!("Failed to emit meter frame event: {}", err));
+    }
+}
+
+/// The channel meter of a strip. The meter store only holds input channels, so actors on
+/// other strips (aux inputs) are not metered.
+fn metered_channel(strip: &WingStripId) -> Option<WingChannelId> {
+    if strip.kind() != WingStripKind::Channel {
+        return None;
+    }
+    WingChannelId::new(strip.number()).ok()
+}
+
+fn meter_source(app_data: &AppData) -> Result<Box<dyn MeterSource>, String> {
+    match app_data.connection.status() {
+        ConsoleStatus::Connected(address) => Ok(Box::new(WingMeterSource::new(address.clone()))),
+        ConsoleStatus::Simulated => Ok(Box::new(SimulatedMeterSource::new(
+            SIMULATED_METER_INTERVAL,
+        ))),
+        _ => Err("Console not connected".to_string()),
+    }
+}
+
+/// Opens the source for the current console. Connecting may take a while, so it happens on a
+/// blocking thread and without holding the app state.
+async fn open_meter_source(
+    state: &MutableState<'_, AppData>,
+) -> Result<Box<dyn MeterSource>, String> {
+    let mut source = meter_source(&*state.read().await)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        source.open()?;
+        Ok::<_, WingError>(source)
+    })
+    .await
+    .map_err(|err| err.to_string())?
+    .map_err(|err| err.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn start_metering(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+) -> Result<(), String> {
+    let source = open_meter_source(&state)
+        .await
+        .map_err(|err| format!("Failed to start metering: {}", err))?;
+
+    let mut app_data = state.write().await;
+    let frame_rate = app_data.preferences.meter_frame_rate();
+    app_data.metering.start(&handle, source, frame_rate);
+
+    let unmetered = app_data
+        .show
+        .mix_config
+        .actors()
+        .filter(|(_, actor)| metered_channel(actor.channel()).is_none())
+        .map(|(_, actor)| actor.name())
+        .join(", ");
+    if !unmetered.is_empty() {
+        log::info!(
+            "Not metering actors on strips without meters: {}",
+            unmetered
+        );
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn stop_metering(state: MutableState<'_, AppData>) -> Result<(), String> {
+    state.write().await.metering.stop();
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_meter_frame_rate(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    frame_rate: u32,
+) -> Result<(), String> {
+    if !(1..=MAX_METER_FRAME_RATE).contains(&frame_rate) {
+        return Err(format!(
+            "Frame rate must be between 1 and {}",
+            MAX_METER_FRAME_RATE
+        ));
+    }
+
+    let mut app_data = state.write().await;
+
+    app_data.preferences.meter_frame_rate = Some(frame_rate);
+    app_data.preferences.save(&handle)?;
+
+    if app_data.metering.is_running() {
+        app_data.metering.set_frame_rate(&handle, frame_rate);
+    }
+
+    Ok(())
+}
@@ -1,4 +1,10 @@
-use crate::wing::{WingChannelId, WingChannelInfo, WingColor};
+use std::collections::BTreeSet;
+
+use crate::{
+    mix::error::MixError,
+    utils::Decibel,
+    wing::{MixerBackend, WingChannelInfo, WingColor, WingMuteGroupId, WingStripId},
+};
 
 #[repr(transparent)]
 #[derive(
@@ -39,20 +45,40 @@ impl std::fmt::Display for ActorId {
 #[serde(rename_all = "camelCase")]
 pub struct Actor {
     name: String,
-    channel: WingChannelId,
+    channel: WingStripId,
     color: Option<WingColor>,
+    /// Correction on top of the desk trim of the actor's channel, adjusted nightly to compensate
+    /// for drifting radio mics
+    #[serde(default)]
+    gain_offset: Decibel,
+    /// The gain offset that has been applied to the desk trim, and to which channel. It differs
+    /// from `gain_offset` while the change could not be applied, e.g. without a console.
+    #[serde(default)]
+    applied_gain_offset: Option<AppliedGainOffset>,
+    #[serde(default)]
+    mute_groups: BTreeSet<WingMuteGroupId>,
+}
+
+#[derive(Copy, Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct AppliedGainOffset {
+    channel: WingStripId,
+    offset: Decibel,
 }
 
 impl Actor {
-    pub fn new(channel: WingChannelId, name: String, color: Option<WingColor>) -> Self {
+    pub fn new(channel: WingStripId, name: String, color: Option<WingColor>) -> Self {
         Self {
             name,
             channel,
             color,
+            gain_offset: Decibel::UNITY,
+            applied_gain_offset: None,
+            mute_groups: BTreeSet::new(),
         }
     }
 
-    pub fn from_channel_info(channel_id: WingChannelId, channel_info: WingChannelInfo) -> Self {
+    pub fn from_channel_info(channel_id: WingStripId, channel_info: WingChannelInfo) -> Self {
         Self::new(channel_id, channel_info.name, Some(channel_info.color))
     }
 
@@ -60,17 +86,161 @@ impl Actor {
         &self.name
     }
 
-    pub fn channel(&self) -> &WingChannelId {
+    pub fn channel(&self) -> &WingStripId {
         &self.channel
     }
 
     pub fn color(&self) -> Option<WingColor> {
         self.color
     }
+
+    pub fn update(&mut self, channel: WingStripId, name: String, color: Option<WingColor>) {
+        self.channel = channel;
+        self.name = name;
+        self.color = color;
+    }
+
+    pub fn gain_offset(&self) -> Decibel {
+        self.gain_offset
+    }
+
+    pub fn set_gain_offset(&mut self, gain_offset: Decibel) {
+        self.gain_offset = gain_offset;
+    }
+
+    pub fn mute_groups(&self) -> impl Iterator<Item = &WingMuteGroupId> {
+        self.mute_groups.iter()
+    }
+
+    pub fn set_mute_groups(&mut self, mute_groups: impl IntoIterator<Item = WingMuteGroupId>) {
+        self.mute_groups = mute_groups.into_iter().collect();
+    }
+
+    /// Assigns the actor's channel to exactly its mute groups.
+    pub async fn apply_mute_groups(&self, wing: &dyn MixerBackend) -> Result<(), MixError> {
+        wing.channel(self.channel)
+            .set_mute_groups(self.mute_groups.iter().copied())
+            .await?;
+        Ok(())
+    }
+
+    /// Brings the desk trim in line with the gain offset. The trim is moved by the difference
+    /// to the offset applied before, so the trim set on the desk is kept. If the actor moved
+    /// to another channel, the offset is taken off the old channel first. Strips without an
+    /// input are left alone.
+    pub async fn apply_gain_offset(&mut self, wing: &dyn MixerBackend) -> Result<(), MixError> {
+        if let Some(applied) = self.applied_gain_offset {
+            if applied.channel != self.channel {
+                shift_trim(wing, applied.channel, -applied.offset.as_f32()).await?;
+                self.applied_gain_offset = None;
+            }
+        }
+
+        if !self.channel.kind().has_source() {
+            return Ok(());
+        }
+
+        let applied = self
+            .applied_gain_offset
+            .map(|applied| applied.offset)
+            .unwrap_or(Decibel::UNITY);
+        if applied != self.gain_offset {
+            let change = self.gain_offset.as_f32() - applied.as_f32();
+            shift_trim(wing, self.channel, change).await?;
+            self.applied_gain_offset = Some(AppliedGainOffset {
+                channel: self.channel,
+                offset: self.gain_offset,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+async fn shift_trim(
+    wing: &dyn MixerBackend,
+    channel: WingStripId,
+    change: f32,
+) -> Result<(), MixError> {
+    let channel = wing.channel(channel);
+    let trim = channel.get_trim().await?;
+    channel.set_trim(trim + change)?;
+    Ok(())
 }
 
 #[derive(Clone, serde::Deserialize, serde::Serialize, specta::Type, tauri_specta::Event)]
 pub enum ActorEvent {
     Added(ActorId, Actor),
+    Updated(ActorId, Actor),
     Removed(ActorId),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wing::{SimulatedWing, WingStripKind, WingValue};
+
+    fn channel(number: u8) -> WingStripId {
+        WingStripId::new(WingStripKind::Channel, number).unwrap()
+    }
+
+    fn trim(wing: &SimulatedWing, number: u8) -> Option<WingValue> {
+        wing.value_at(&format!("/ch/{}/in/set/trim", number))
+    }
+
+    #[test]
+    fn gain_offset_moves_the_desk_trim_once() {
+        let wing = SimulatedWing::default();
+        let backend: &dyn MixerBackend = &wing;
+        backend
+            .channel(channel(1))
+            .set_trim(Decibel::new(2.0))
+            .unwrap();
+
+        let mut actor = Actor::new(channel(1), "Alice".to_string(), None);
+        tauri::async_runtime::block_on(async {
+            actor.set_gain_offset(Decibel::new(3.0));
+            actor.apply_gain_offset(backend).await.unwrap();
+            actor.apply_gain_offset(backend).await.unwrap();
+            assert_eq!(trim(&wing, 1), Some(WingValue::Float(5.0)));
+
+            actor.set_gain_offset(Decibel::new(1.0));
+            actor.apply_gain_offset(backend).await.unwrap();
+            assert_eq!(trim(&wing, 1), Some(WingValue::Float(3.0)));
+        });
+    }
+
+    #[test]
+    fn gain_offset_moves_with_the_actor() {
+        let wing = SimulatedWing::default();
+        let backend: &dyn MixerBackend = &wing;
+
+        let mut actor = Actor::new(channel(1), "Alice".to_string(), None);
+        tauri::async_runtime::block_on(async {
+            actor.set_gain_offset(Decibel::new(4.0));
+            actor.apply_gain_offset(backend).await.unwrap();
+
+            actor.update(channel(2), "Alice".to_string(), None);
+            actor.apply_gain_offset(backend).await.unwrap();
+        });
+
+        assert_eq!(trim(&wing, 1), Some(WingValue::Float(0.0)));
+        assert_eq!(trim(&wing, 2), Some(WingValue::Float(4.0)));
+    }
+
+    #[test]
+    fn trim_is_clamped_to_its_range() {
+        let wing = SimulatedWing::default();
+        let backend: &dyn MixerBackend = &wing;
+        backend
+            .channel(channel(1))
+            .set_trim(Decibel::new(16.0))
+            .unwrap();
+
+        let mut actor = Actor::new(channel(1), "Alice".to_string(), None);
+        actor.set_gain_offset(Decibel::new(6.0));
+        tauri::async_runtime::block_on(actor.apply_gain_offset(backend)).unwrap();
+
+        assert_eq!(trim(&wing, 1), Some(WingValue::Float(18.0)));
+    }
+}
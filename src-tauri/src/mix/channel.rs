@@ -1,5 +1,10 @@
 use crate::{
-    wing::{WingChannelId, WingChannelInfo},
+    utils::Decibel,
+    wing::{
+        id::WingId, MixerBackend, WingBusId, WingChannelInfo, WingDynamics, WingEq,
+        WingInputSource, WingMuteGroupId, WingParameterBlock, WingParameterDifference, WingSend,
+        WingStripId,
+    },
     AppData, MutableState,
 };
 
@@ -7,20 +12,285 @@ use crate::{
 #[specta::specta]
 pub async fn get_wing_channel_info(
     state: MutableState<'_, AppData>,
-    channel: u8,
+    channel: WingStripId,
 ) -> Result<WingChannelInfo, String> {
     let mut app_data = state.write().await;
     let Some(wing) = app_data.console.as_mut() else {
         return Err("Console not connected".to_string());
     };
 
-    let channel_id: WingChannelId = channel
-        .try_into()
-        .map_err(|err| format!("Invalid channel ID: {}", err))?;
-
-    let channel = wing.channel(channel_id);
+    let channel = wing.channel(channel);
     channel
         .get_info()
         .await
         .map_err(|err| format!("Failed to get channel info: {}", err))
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_channel_eq(
+    state: MutableState<'_, AppData>,
+    channel: WingStripId,
+) -> Result<WingEq, String> {
+    let app_data = state.read().await;
+    let Some(wing) = app_data.console.as_ref() else {
+        return Err("Console not connected".to_string());
+    };
+
+    wing.channel(channel)
+        .get_eq()
+        .await
+        .map_err(|err| format!("Failed to get channel EQ: {}", err))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_channel_eq(
+    state: MutableState<'_, AppData>,
+    channel: WingStripId,
+    eq: WingEq,
+) -> Result<(), String> {
+    let app_data = state.read().await;
+    let Some(wing) = app_data.console.as_ref() else {
+        return Err("Console not connected".to_string());
+    };
+
+    let transaction = wing.transaction();
+    let backend: &dyn MixerBackend = &transaction;
+    backend
+        .channel(channel)
+        .set_eq(&eq)
+        .map_err(|err| format!("Failed to set channel EQ: {}", err))?;
+    transaction
+        .commit()
+        .map_err(|err| format!("Failed to set channel EQ: {}", err))?;
+
+    Ok(())
+}
+
+/// Lists the parameters in which the channel's current EQ differs from `eq`.
+#[tauri::command]
+#[specta::specta]
+pub async fn compare_channel_eq(
+    state: MutableState<'_, AppData>,
+    channel: WingStripId,
+    eq: WingEq,
+) -> Result<Vec<WingParameterDifference>, String> {
+    let app_data = state.read().await;
+    let Some(wing) = app_data.console.as_ref() else {
+        return Err("Console not connected".to_string());
+    };
+
+    let current = wing
+        .channel(channel)
+        .get_eq()
+        .await
+        .map_err(|err| format!("Failed to get channel EQ: {}", err))?;
+
+    Ok(current.diff(&eq))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_channel_dynamics(
+    state: MutableState<'_, AppData>,
+    channel: WingStripId,
+) -> Result<WingDynamics, String> {
+    let app_data = state.read().await;
+    let Some(wing) = app_data.console.as_ref() else {
+        return Err("Console not connected".to_string());
+    };
+
+    wing.channel(channel)
+        .get_dynamics()
+        .await
+        .map_err(|err| format!("Failed to get channel dynamics: {}", err))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_channel_dynamics(
+    state: MutableState<'_, AppData>,
+    channel: WingStripId,
+    dynamics: WingDynamics,
+) -> Result<(), String> {
+    let app_data = state.read().await;
+    let Some(wing) = app_data.console.as_ref() else {
+        return Err("Console not connected".to_string());
+    };
+
+    let transaction = wing.transaction();
+    let backend: &dyn MixerBackend = &transaction;
+    backend
+        .channel(channel)
+        .set_dynamics(&dynamics)
+        .map_err(|err| format!("Failed to set channel dynamics: {}", err))?;
+    transaction
+        .commit()
+        .map_err(|err| format!("Failed to set channel dynamics: {}", err))?;
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_channel_send(
+    state: MutableState<'_, AppData>,
+    channel: WingStripId,
+    bus: WingBusId,
+) -> Result<WingSend, String> {
+    let app_data = state.read().await;
+    let Some(wing) = app_data.console.as_ref() else {
+        return Err("Console not connected".to_string());
+    };
+
+    wing.channel(channel)
+        .get_send(bus)
+        .await
+        .map_err(|err| format!("Failed to get channel send: {}", err))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_channel_send(
+    state: MutableState<'_, AppData>,
+    channel: WingStripId,
+    bus: WingBusId,
+    send: WingSend,
+) -> Result<(), String> {
+    let app_data = state.read().await;
+    let Some(wing) = app_data.console.as_ref() else {
+        return Err("Console not connected".to_string());
+    };
+
+    let transaction = wing.transaction();
+    let backend: &dyn MixerBackend = &transaction;
+    backend
+        .channel(channel)
+        .set_send(bus, &send)
+        .map_err(|err| format!("Failed to set channel send: {}", err))?;
+    transaction
+        .commit()
+        .map_err(|err| format!("Failed to set channel send: {}", err))?;
+
+    Ok(())
+}
+
+#[derive(serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ChannelGain {
+    pub trim: Decibel,
+    pub source: Option<WingInputSource>,
+    /// Gain of the source input, `None` if the channel is not connected to an input
+    pub input_gain: Option<Decibel>,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_channel_gain(
+    state: MutableState<'_, AppData>,
+    channel: WingStripId,
+) -> Result<ChannelGain, String> {
+    let app_data = state.read().await;
+    let Some(wing) = app_data.console.as_ref() else {
+        return Err("Console not connected".to_string());
+    };
+
+    let channel = wing.channel(channel);
+    let trim = channel
+        .get_trim()
+        .await
+        .map_err(|err| format!("Failed to get channel trim: {}", err))?;
+    let source = channel
+        .get_input_source()
+        .await
+        .map_err(|err| format!("Failed to get channel source: {}", err))?;
+    let input_gain = match source {
+        Some(_) => Some(
+            channel
+                .get_input_gain()
+                .await
+                .map_err(|err| format!("Failed to get input gain: {}", err))?,
+        ),
+        None => None,
+    };
+
+    Ok(ChannelGain {
+        trim,
+        source,
+        input_gain,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_channel_trim(
+    state: MutableState<'_, AppData>,
+    channel: WingStripId,
+    trim: Decibel,
+) -> Result<(), String> {
+    let app_data = state.read().await;
+    let Some(wing) = app_data.console.as_ref() else {
+        return Err("Console not connected".to_string());
+    };
+
+    wing.channel(channel)
+        .set_trim(trim)
+        .map_err(|err| format!("Failed to set channel trim: {}", err))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_channel_input_gain(
+    state: MutableState<'_, AppData>,
+    channel: WingStripId,
+    gain: Decibel,
+) -> Result<(), String> {
+    let app_data = state.read().await;
+    let Some(wing) = app_data.console.as_ref() else {
+        return Err("Console not connected".to_string());
+    };
+
+    wing.channel(channel)
+        .set_input_gain(gain)
+        .await
+        .map_err(|err| format!("Failed to set input gain: {}", err))
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_mute_group_states(state: MutableState<'_, AppData>) -> Result<Vec<bool>, String> {
+    let app_data = state.read().await;
+    let Some(wing) = app_data.console.as_ref() else {
+        return Err("Console not connected".to_string());
+    };
+
+    let mut states = Vec::new();
+    for i in WingMuteGroupId::MIN_ID..=WingMuteGroupId::MAX_ID {
+        let muted = wing
+            .mute_group(i.try_into().unwrap())
+            .is_muted()
+            .await
+            .map_err(|err| format!("Failed to get mute group state: {}", err))?;
+        states.push(muted);
+    }
+
+    Ok(states)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_mute_group_state(
+    state: MutableState<'_, AppData>,
+    mute_group: WingMuteGroupId,
+    muted: bool,
+) -> Result<(), String> {
+    let app_data = state.read().await;
+    let Some(wing) = app_data.console.as_ref() else {
+        return Err("Console not connected".to_string());
+    };
+
+    wing.mute_group(mute_group)
+        .set_muted(muted)
+        .map_err(|err| format!("Failed to set mute group state: {}", err))
+}
@@ -6,9 +6,11 @@ use tauri_specta::Event;
 use crate::{
     mix::{
         error::MixError, Actor, ActorEvent, ActorId, Group, GroupEvent, GroupId, Position,
-        PositionId,
+        PositionEvent, PositionId, Stage, StageEvent,
     },
-    wing::{id::WingId, WingChannelId, WingColor},
+    show::ShowEvent,
+    utils::Decibel,
+    wing::{MixerBackend, WingColor, WingMuteGroupId, WingStripId},
     AppData, MutableState,
 };
 
@@ -18,6 +20,8 @@ pub struct MixConfig {
     actors: HashMap<ActorId, Actor>,
     groups: HashMap<GroupId, Group>,
     positions: HashMap<PositionId, Position>,
+    #[serde(default)]
+    stage: Stage,
 }
 
 impl MixConfig {
@@ -29,6 +33,10 @@ impl MixConfig {
         self.actors.get(&id).ok_or(MixError::ActorNotFound(id))
     }
 
+    pub fn actor_mut(&mut self, id: ActorId) -> Result<&mut Actor, MixError> {
+        self.actors.get_mut(&id).ok_or(MixError::ActorNotFound(id))
+    }
+
     pub fn groups(&self) -> impl Iterator<Item = (&GroupId, &Group)> {
         self.groups.iter()
     }
@@ -47,7 +55,24 @@ impl MixConfig {
             .ok_or(MixError::PositionNotFound(id))
     }
 
-    pub fn controlled_channels<'a>(&'a self) -> impl Iterator<Item = &'a WingChannelId> {
+    pub fn stage(&self) -> &Stage {
+        &self.stage
+    }
+
+    pub fn set_stage(&mut self, stage: Stage) {
+        self.stage = stage;
+    }
+
+    /// Applies the gain offsets that have not reached the console yet, see
+    /// [`Actor::apply_gain_offset`].
+    pub async fn apply_gain_offsets(&mut self, wing: &dyn MixerBackend) -> Result<(), MixError> {
+        for actor in self.actors.values_mut() {
+            actor.apply_gain_offset(wing).await?;
+        }
+        Ok(())
+    }
+
+    pub fn controlled_channels<'a>(&'a self) -> impl Iterator<Item = &'a WingStripId> {
         self.actors.values().map(|actor| actor.channel())
     }
 }
@@ -67,7 +92,7 @@ impl MixConfig {
 
     pub fn add_actor(
         &mut self,
-        channel: WingChannelId,
+        channel: WingStripId,
         name: String,
         color: Option<WingColor>,
     ) -> (ActorId, Actor) {
@@ -77,6 +102,36 @@ impl MixConfig {
         (id, actor)
     }
 
+    pub fn update_actor(
+        &mut self,
+        id: ActorId,
+        channel: WingStripId,
+        name: String,
+        color: Option<WingColor>,
+    ) -> Result<Actor, MixError> {
+        let actor = self.actor_mut(id)?;
+        actor.update(channel, name, color);
+        Ok(actor.clone())
+    }
+
+    /// Removes an actor and its group memberships. Returns the actor and the groups that
+    /// changed.
+    pub fn remove_actor(&mut self, id: ActorId) -> Result<(Actor, Vec<GroupId>), MixError> {
+        let actor = self.actors.remove(&id).ok_or(MixError::ActorNotFound(id))?;
+
+        let groups = self
+            .groups
+            .iter_mut()
+            .filter_map(|(group_id, group)| group.remove_actor(id).then_some(*group_id))
+            .collect();
+
+        Ok((actor, groups))
+    }
+
+    pub fn group_mut(&mut self, id: GroupId) -> Result<&mut Group, MixError> {
+        self.groups.get_mut(&id).ok_or(MixError::GroupNotFound(id))
+    }
+
     pub fn add_group(
         &mut self,
         actors: Vec<ActorId>,
@@ -94,6 +149,67 @@ impl MixConfig {
         self.groups.insert(id, group.clone());
         (id, group)
     }
+
+    pub fn update_group(
+        &mut self,
+        id: GroupId,
+        name: String,
+        color: Option<WingColor>,
+    ) -> Result<Group, MixError> {
+        let group = self.group_mut(id)?;
+        group.update(name, color);
+        Ok(group.clone())
+    }
+
+    pub fn add_group_member(&mut self, id: GroupId, actor_id: ActorId) -> Result<Group, MixError> {
+        self.actor(actor_id)?;
+
+        let group = self.group_mut(id)?;
+        group.add_actor(actor_id);
+        Ok(group.clone())
+    }
+
+    pub fn remove_group_member(
+        &mut self,
+        id: GroupId,
+        actor_id: ActorId,
+    ) -> Result<Group, MixError> {
+        let group = self.group_mut(id)?;
+        group.remove_actor(actor_id);
+        Ok(group.clone())
+    }
+
+    pub fn remove_group(&mut self, id: GroupId) -> Result<Group, MixError> {
+        self.groups.remove(&id).ok_or(MixError::GroupNotFound(id))
+    }
+
+    pub fn add_position(&mut self, name: String) -> (PositionId, Position) {
+        let id: PositionId = self
+            .positions
+            .keys()
+            .max()
+            .map(|id| id.next())
+            .unwrap_or_default();
+        let position = Position::new(name);
+
+        self.positions.insert(id, position.clone());
+        (id, position)
+    }
+
+    pub fn update_position(&mut self, id: PositionId, position: Position) -> Result<(), MixError> {
+        let current = self
+            .positions
+            .get_mut(&id)
+            .ok_or(MixError::PositionNotFound(id))?;
+        *current = position;
+        Ok(())
+    }
+
+    pub fn remove_position(&mut self, id: PositionId) -> Result<Position, MixError> {
+        self.positions
+            .remove(&id)
+            .ok_or(MixError::PositionNotFound(id))
+    }
 }
 
 #[tauri::command]
@@ -101,7 +217,7 @@ impl MixConfig {
 pub async fn add_actor(
     handle: AppHandle,
     state: MutableState<'_, AppData>,
-    channel: WingChannelId,
+    channel: WingStripId,
     name: String,
     color: Option<WingColor>,
 ) -> Result<ActorId, String> {
@@ -110,19 +226,122 @@ pub async fn add_actor(
     let (id, actor) = app_data.show.mix_config.add_actor(channel, name, color);
     let _ = ActorEvent::Added(id, actor)
         .emit(&handle)
-        .inspect_err(|err| println!("Failed to emit actor added event: {}", err));
+        .inspect_err(|err| log::error!("Failed to emit actor added event: {}", err));
 
     Ok(id)
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn update_actor(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    actor_id: ActorId,
+    channel: WingStripId,
+    name: String,
+    color: Option<WingColor>,
+) -> Result<(), String> {
+    let mut app_data = state.write().await;
+    let app_data = &mut *app_data;
+
+    app_data
+        .show
+        .mix_config
+        .update_actor(actor_id, channel, name, color)
+        .map_err(|err| format!("Failed to update actor: {}", err))?;
+
+    // Moves the gain offset along if the actor changed channels
+    let actor = app_data
+        .show
+        .mix_config
+        .actor_mut(actor_id)
+        .map_err(|err| format!("Failed to update actor: {}", err))?;
+    let res = match app_data.console.as_ref() {
+        Some(console) => actor.apply_gain_offset(console.as_ref()).await,
+        None => Ok(()),
+    };
+
+    let _ = ActorEvent::Updated(actor_id, actor.clone())
+        .emit(&handle)
+        .inspect_err(|err| log::error!("Failed to emit actor updated event: {}", err));
+
+    res.map_err(|err| format!("Failed to apply gain offset: {}", err))
+}
+
+/// Deletes an actor. If cues still use the actor, the deletion is refused with a list of
+/// these cues, unless `remove_from_cues` is set. Group memberships are always removed.
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_actor(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    actor_id: ActorId,
+    remove_from_cues: bool,
+) -> Result<(), String> {
+    let mut app_data = state.write().await;
+
+    app_data
+        .show
+        .mix_config
+        .actor(actor_id)
+        .map_err(|err| format!("Failed to delete actor: {}", err))?;
+
+    let affected_cues = app_data
+        .show
+        .cues
+        .iter()
+        .filter(|cue| cue.uses_actor(actor_id))
+        .map(|cue| cue.id.to_string())
+        .collect::<Vec<_>>();
+    if !affected_cues.is_empty() && !remove_from_cues {
+        return Err(format!(
+            "Actor is used in cues {}",
+            affected_cues.join(", ")
+        ));
+    }
+
+    let (_, groups) = app_data
+        .show
+        .mix_config
+        .remove_actor(actor_id)
+        .map_err(|err| format!("Failed to delete actor: {}", err))?;
+
+    for cue in app_data.show.cues.iter_mut() {
+        if cue.remove_actor(actor_id) {
+            let _ = ShowEvent::CueUpdated(cue.clone())
+                .emit(&handle)
+                .inspect_err(|err| log::error!("Failed to emit cue updated event: {}", err));
+        }
+    }
+
+    for group_id in groups {
+        let Ok(group) = app_data.show.mix_config.group(group_id) else {
+            continue;
+        };
+        let _ = GroupEvent::Updated(group_id, group.clone())
+            .emit(&handle)
+            .inspect_err(|err| log::error!("Failed to emit group updated event: {}", err));
+    }
+
+    let _ = ActorEvent::Removed(actor_id)
+        .emit(&handle)
+        .inspect_err(|err| log::error!("Failed to emit actor removed event: {}", err));
+
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn import_actors(
     handle: AppHandle,
     state: MutableState<'_, AppData>,
-    from_channel: WingChannelId,
-    to_channel: WingChannelId,
+    from_channel: WingStripId,
+    to_channel: WingStripId,
 ) -> Result<(), String> {
+    if from_channel.kind() != to_channel.kind() {
+        return Err("Cannot import actors across different strip types".to_string());
+    }
+
     let mut app_data = state.write().await;
 
     let Some(console) = app_data.console.as_ref() else {
@@ -130,8 +349,8 @@ pub async fn import_actors(
     };
 
     let mut actors = Vec::new();
-    for i in from_channel.value()..=to_channel.value() {
-        let channel_id: WingChannelId = i.try_into().unwrap();
+    for i in from_channel.number()..=to_channel.number() {
+        let channel_id = WingStripId::new(from_channel.kind(), i).unwrap();
         let channel = console.channel(channel_id);
         let Some(channel_info) = channel.get_info().await.ok() else {
             continue;
@@ -145,7 +364,7 @@ pub async fn import_actors(
 
         let _ = ActorEvent::Added(id, actor)
             .emit(&handle)
-            .inspect_err(|err| println!("Failed to emit actor added event: {}", err));
+            .inspect_err(|err| log::error!("Failed to emit actor added event: {}", err));
     }
 
     Ok(())
@@ -165,7 +384,310 @@ pub async fn add_group(
     let (id, group) = app_data.show.mix_config.add_group(actors, name, color);
     let _ = GroupEvent::Added(id, group)
         .emit(&handle)
-        .inspect_err(|err| println!("Failed to emit group added event: {}", err));
+        .inspect_err(|err| log::error!("Failed to emit group added event: {}", err));
 
     Ok(id)
 }
+
+/// Stores the actor's nightly gain offset and applies it right away if a console is connected.
+/// Otherwise, or if applying fails, it is applied once the console is connected again.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_actor_gain_offset(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    actor_id: ActorId,
+    gain_offset: Decibel,
+) -> Result<Actor, String> {
+    let mut app_data = state.write().await;
+    let app_data = &mut *app_data;
+
+    let actor = app_data
+        .show
+        .mix_config
+        .actor_mut(actor_id)
+        .map_err(|err| format!("Failed to set gain offset: {}", err))?;
+    actor.set_gain_offset(gain_offset);
+    let res = match app_data.console.as_ref() {
+        Some(console) => actor.apply_gain_offset(console.as_ref()).await,
+        None => Ok(()),
+    };
+    let actor = actor.clone();
+
+    let _ = ActorEvent::Updated(actor_id, actor.clone())
+        .emit(&handle)
+        .inspect_err(|err| log::error!("Failed to emit actor updated event: {}", err));
+
+    res.map_err(|err| format!("Failed to apply gain offset: {}", err))?;
+    Ok(actor)
+}
+
+/// Stores the mute groups of an actor and assigns its channel right away if a console is
+/// connected.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_actor_mute_groups(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    actor_id: ActorId,
+    mute_groups: Vec<WingMuteGroupId>,
+) -> Result<Actor, String> {
+    let mut app_data = state.write().await;
+
+    let actor = app_data
+        .show
+        .mix_config
+        .actor_mut(actor_id)
+        .map_err(|err| format!("Failed to set mute groups: {}", err))?;
+    actor.set_mute_groups(mute_groups);
+    let actor = actor.clone();
+
+    if let Some(console) = app_data.console.as_ref() {
+        actor
+            .apply_mute_groups(console.as_ref())
+            .await
+            .map_err(|err| format!("Failed to apply mute groups: {}", err))?;
+    }
+
+    let _ = ActorEvent::Updated(actor_id, actor.clone())
+        .emit(&handle)
+        .inspect_err(|err| log::error!("Failed to emit actor updated event: {}", err));
+
+    Ok(actor)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_group(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    group_id: GroupId,
+    name: String,
+    color: Option<WingColor>,
+) -> Result<(), String> {
+    let mut app_data = state.write().await;
+
+    let group = app_data
+        .show
+        .mix_config
+        .update_group(group_id, name, color)
+        .map_err(|err| format!("Failed to update group: {}", err))?;
+
+    let _ = GroupEvent::Updated(group_id, group)
+        .emit(&handle)
+        .inspect_err(|err| log::error!("Failed to emit group updated event: {}", err));
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn add_group_member(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    group_id: GroupId,
+    actor_id: ActorId,
+) -> Result<(), String> {
+    let mut app_data = state.write().await;
+
+    let group = app_data
+        .show
+        .mix_config
+        .add_group_member(group_id, actor_id)
+        .map_err(|err| format!("Failed to add group member: {}", err))?;
+
+    let _ = GroupEvent::Updated(group_id, group)
+        .emit(&handle)
+        .inspect_err(|err| log::error!("Failed to emit group updated event: {}", err));
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_group_member(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    group_id: GroupId,
+    actor_id: ActorId,
+) -> Result<(), String> {
+    let mut app_data = state.write().await;
+
+    let group = app_data
+        .show
+        .mix_config
+        .remove_group_member(group_id, actor_id)
+        .map_err(|err| format!("Failed to remove group member: {}", err))?;
+
+    let _ = GroupEvent::Updated(group_id, group)
+        .emit(&handle)
+        .inspect_err(|err| log::error!("Failed to emit group updated event: {}", err));
+
+    Ok(())
+}
+
+/// Deletes a group. If cues still assign the group, the deletion is refused with a list of
+/// these cues, unless `remove_from_cues` is set.
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_group(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    group_id: GroupId,
+    remove_from_cues: bool,
+) -> Result<(), String> {
+    let mut app_data = state.write().await;
+
+    app_data
+        .show
+        .mix_config
+        .group(group_id)
+        .map_err(|err| format!("Failed to delete group: {}", err))?;
+
+    let affected_cues = app_data
+        .show
+        .cues
+        .iter()
+        .filter(|cue| cue.uses_group(group_id))
+        .map(|cue| cue.id.to_string())
+        .collect::<Vec<_>>();
+    if !affected_cues.is_empty() && !remove_from_cues {
+        return Err(format!(
+            "Group is used in cues {}",
+            affected_cues.join(", ")
+        ));
+    }
+
+    app_data
+        .show
+        .mix_config
+        .remove_group(group_id)
+        .map_err(|err| format!("Failed to delete group: {}", err))?;
+
+    for cue in app_data.show.cues.iter_mut() {
+        if cue.remove_group(group_id) {
+            let _ = ShowEvent::CueUpdated(cue.clone())
+                .emit(&handle)
+                .inspect_err(|err| log::error!("Failed to emit cue updated event: {}", err));
+        }
+    }
+
+    let _ = GroupEvent::Removed(group_id)
+        .emit(&handle)
+        .inspect_err(|err| log::error!("Failed to emit group removed event: {}", err));
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn add_position(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    name: String,
+) -> Result<PositionId, String> {
+    let mut app_data = state.write().await;
+
+    let (id, position) = app_data.show.mix_config.add_position(name);
+    let _ = PositionEvent::Added(id, position)
+        .emit(&handle)
+        .inspect_err(|err| log::error!("Failed to emit position added event: {}", err));
+
+    Ok(id)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_position(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    position_id: PositionId,
+    position: Position,
+) -> Result<(), String> {
+    let mut app_data = state.write().await;
+
+    app_data
+        .show
+        .mix_config
+        .update_position(position_id, position.clone())
+        .map_err(|err| format!("Failed to update position: {}", err))?;
+
+    let _ = PositionEvent::Updated(position_id, position)
+        .emit(&handle)
+        .inspect_err(|err| log::error!("Failed to emit position updated event: {}", err));
+
+    Ok(())
+}
+
+/// Deletes a position. If cues still assign the position, the deletion is refused with a list
+/// of these cues, unless `remove_from_cues` is set.
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_position(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    position_id: PositionId,
+    remove_from_cues: bool,
+) -> Result<(), String> {
+    let mut app_data = state.write().await;
+
+    app_data
+        .show
+        .mix_config
+        .position(position_id)
+        .map_err(|err| format!("Failed to delete position: {}", err))?;
+
+    let affected_cues = app_data
+        .show
+        .cues
+        .iter()
+        .filter(|cue| cue.uses_position(position_id))
+        .map(|cue| cue.id.to_string())
+        .collect::<Vec<_>>();
+    if !affected_cues.is_empty() && !remove_from_cues {
+        return Err(format!(
+            "Position is used in cues {}",
+            affected_cues.join(", ")
+        ));
+    }
+
+    app_data
+        .show
+        .mix_config
+        .remove_position(position_id)
+        .map_err(|err| format!("Failed to delete position: {}", err))?;
+
+    for cue in app_data.show.cues.iter_mut() {
+        if cue.remove_position(position_id) {
+            let _ = ShowEvent::CueUpdated(cue.clone())
+                .emit(&handle)
+                .inspect_err(|err| log::error!("Failed to emit cue updated event: {}", err));
+        }
+    }
+
+    let _ = PositionEvent::Removed(position_id)
+        .emit(&handle)
+        .inspect_err(|err| log::error!("Failed to emit position removed event: {}", err));
+
+    Ok(())
+}
+
+/// Replaces the stage geometry. Positions on stage pick up the new geometry the next time they
+/// are applied.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_stage(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    stage: Stage,
+) -> Result<(), String> {
+    let mut app_data = state.write().await;
+
+    app_data.show.mix_config.set_stage(stage.clone());
+
+    let _ = StageEvent::Updated(stage)
+        .emit(&handle)
+        .inspect_err(|err| log::error!("Failed to emit stage updated event: {}", err));
+
+    Ok(())
+}
@@ -12,6 +12,7 @@ pub enum MixError {
     PositionNotFound(PositionId),
 
     DcaIndexOutOfRange(u8),
+    MuteGroupIndexOutOfRange(u8),
 }
 
 impl std::fmt::Display for MixError {
@@ -24,6 +25,9 @@ impl std::fmt::Display for MixError {
             Self::PositionNotFound(id) => write!(f, "Position not found: {}", id),
 
             Self::DcaIndexOutOfRange(idx) => write!(f, "DCA index {} is out of range", idx),
+            Self::MuteGroupIndexOutOfRange(idx) => {
+                write!(f, "Mute group index {} is out of range", idx)
+            }
         }
     }
 }
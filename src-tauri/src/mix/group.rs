@@ -69,10 +69,26 @@ impl Group {
     pub fn color(&self) -> Option<WingColor> {
         self.color
     }
+
+    pub fn update(&mut self, name: String, color: Option<WingColor>) {
+        self.name = name;
+        self.color = color;
+    }
+
+    /// Returns whether the actor was not a member yet.
+    pub fn add_actor(&mut self, actor_id: ActorId) -> bool {
+        self.actors.insert(actor_id)
+    }
+
+    /// Returns whether the actor was a member.
+    pub fn remove_actor(&mut self, actor_id: ActorId) -> bool {
+        self.actors.remove(&actor_id)
+    }
 }
 
 #[derive(Clone, serde::Deserialize, serde::Serialize, specta::Type, tauri_specta::Event)]
 pub enum GroupEvent {
     Added(GroupId, Group),
+    Updated(GroupId, Group),
     Removed(GroupId),
 }
@@ -7,6 +7,9 @@ pub use group::*;
 mod position;
 pub use position::*;
 
+mod stage;
+pub use stage::*;
+
 mod config;
 pub use config::*;
 
@@ -14,3 +17,6 @@ pub mod error;
 
 mod channel;
 pub use channel::*;
+
+mod sync;
+pub use sync::*;
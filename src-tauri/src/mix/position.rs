@@ -1,4 +1,10 @@
-use crate::{mix::error::MixError, utils::ClampedValue, wing::WingChannel};
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::{
+    mix::{error::MixError, Stage, StagePoint},
+    utils::{ClampedValue, Decibel},
+    wing::{WingChannel, WingSendTarget},
+};
 
 #[repr(transparent)]
 #[derive(
@@ -38,19 +44,118 @@ impl std::fmt::Display for PositionId {
 #[derive(Clone, serde::Serialize, serde::Deserialize, specta::Type)]
 #[serde(rename_all = "camelCase")]
 pub struct Position {
-    /// Panning. 0.0 = left, 0.5 = center, 1.0 = right
-    pan: ClampedValue,
+    #[serde(default)]
+    name: String,
+    /// Where the position is on stage. Pan and delay are computed from it unless they are
+    /// entered by hand.
+    #[serde(default)]
+    location: Option<StagePoint>,
+    /// Hand entered panning. 0.0 = left, 0.5 = center, 1.0 = right
+    #[serde(default)]
+    pan: Option<ClampedValue>,
+    /// Hand entered channel delay in ms to time align the voice with the actor. Without a delay
+    /// (entered or computed) the channel delay is set to 0 ms.
+    #[serde(default)]
+    delay: Option<f32>,
+    /// Hand entered send levels to fills, delays and surrounds, overriding the levels computed
+    /// from the location.
+    #[serde(default)]
+    sends: Vec<PositionSend>,
+}
+
+#[derive(Clone, Copy, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PositionSend {
+    pub target: WingSendTarget,
+    pub level: Decibel,
 }
 
 impl Default for Position {
     fn default() -> Self {
-        Self { pan: 0.5.into() }
+        Self {
+            name: String::new(),
+            location: None,
+            pan: None,
+            delay: None,
+            sends: Vec::new(),
+        }
     }
 }
 
 impl Position {
-    pub fn apply(&self, channel: &WingChannel) -> Result<(), MixError> {
-        channel.set_pan(self.pan.as_f32())?;
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn location(&self) -> Option<StagePoint> {
+        self.location
+    }
+
+    /// The hand entered pan, else the pan computed from the location, else center.
+    pub fn pan(&self, stage: &Stage) -> ClampedValue {
+        self.pan
+            .or_else(|| self.location.map(|location| stage.pan(&location)))
+            .unwrap_or(ClampedValue::new(0.5))
+    }
+
+    /// The hand entered delay, else the delay computed from the location. `None` means no delay.
+    pub fn delay(&self, stage: &Stage) -> Option<f32> {
+        self.delay
+            .or_else(|| self.location.and_then(|location| stage.delay(&location)))
+    }
+
+    pub fn sends(&self) -> &[PositionSend] {
+        &self.sends
+    }
+
+    /// The send levels computed from the location, with the hand entered ones taking precedence.
+    pub fn send_levels(&self, stage: &Stage) -> BTreeMap<WingSendTarget, Decibel> {
+        let mut levels: BTreeMap<_, _> = self
+            .location
+            .map(|location| stage.send_levels(&location))
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        levels.extend(self.sends.iter().map(|send| (send.target, send.level)));
+        levels
+    }
+
+    /// Sets pan, delay and send levels of the channel. `sends` are the sends that positions
+    /// control; those this position has no level for are set to -inf, so the result does not
+    /// depend on the position the actor was in before.
+    pub fn apply(
+        &self,
+        channel: &WingChannel,
+        stage: &Stage,
+        sends: &BTreeSet<WingSendTarget>,
+    ) -> Result<(), MixError> {
+        channel.set_pan(self.pan(stage).as_f32())?;
+        channel.set_delay(Some(self.delay(stage).unwrap_or(0.0)))?;
+
+        let levels = self.send_levels(stage);
+        let mut targets = sends.clone();
+        targets.extend(levels.keys().copied());
+        for target in targets {
+            let level = levels
+                .get(&target)
+                .copied()
+                .unwrap_or(Decibel::NEG_INFINITY);
+            channel.set_send_level(target, level)?;
+        }
         Ok(())
     }
 }
+
+#[derive(Clone, serde::Deserialize, serde::Serialize, specta::Type, tauri_specta::Event)]
+pub enum PositionEvent {
+    Added(PositionId, Position),
+    Updated(PositionId, Position),
+    Removed(PositionId),
+}
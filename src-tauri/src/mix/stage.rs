@@ -0,0 +1,218 @@
+use crate::{
+    utils::{ClampedValue, Decibel},
+    wing::WingSendTarget,
+};
+
+/// Speed of sound at room temperature in m/s
+const SPEED_OF_SOUND: f32 = 343.0;
+
+/// Distances below this (in m) are treated as this, so a voice right at a speaker does not
+/// attenuate all others to -inf.
+const MIN_SPEAKER_DISTANCE: f32 = 1.0;
+
+/// A point in stage coordinates in metres, as seen from the audience. `x` runs from the house
+/// left edge of the stage to the house right edge, `y` from the downstage edge upstage. Points
+/// in the house have a negative `y`.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Default, serde::Serialize, serde::Deserialize, specta::Type,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct StagePoint {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl StagePoint {
+    pub fn distance(&self, other: &StagePoint) -> f32 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Speaker {
+    pub name: String,
+    pub location: StagePoint,
+    /// Send feeding the speaker, `None` for the main system
+    pub send: Option<WingSendTarget>,
+}
+
+impl Speaker {
+    pub fn is_main(&self) -> bool {
+        self.send.is_none()
+    }
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct Stage {
+    /// Width in metres
+    width: f32,
+    /// Depth in metres
+    depth: f32,
+    speakers: Vec<Speaker>,
+}
+
+impl Default for Stage {
+    fn default() -> Self {
+        Self {
+            width: 10.0,
+            depth: 8.0,
+            speakers: Vec::new(),
+        }
+    }
+}
+
+impl Stage {
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    pub fn depth(&self) -> f32 {
+        self.depth
+    }
+
+    pub fn speakers(&self) -> &[Speaker] {
+        &self.speakers
+    }
+
+    fn main_speakers(&self) -> impl Iterator<Item = &Speaker> {
+        self.speakers.iter().filter(|speaker| speaker.is_main())
+    }
+
+    /// Pan for a voice at `point`: the outermost main speakers are hard left and hard right.
+    /// Without a left and a right main speaker, the edges of the stage are used instead.
+    pub fn pan(&self, point: &StagePoint) -> ClampedValue {
+        let left = self
+            .main_speakers()
+            .map(|speaker| speaker.location.x)
+            .min_by(f32::total_cmp);
+        let right = self
+            .main_speakers()
+            .map(|speaker| speaker.location.x)
+            .max_by(f32::total_cmp);
+
+        let (left, right) = match (left, right) {
+            (Some(left), Some(right)) if right > left => (left, right),
+            _ => (0.0, self.width),
+        };
+        if right <= left {
+            return ClampedValue::new(0.5);
+        }
+
+        ClampedValue::new((point.x - left) / (right - left))
+    }
+
+    /// Channel delay in ms for a voice at `point`, so that the sound of the main system arrives
+    /// no earlier than the voice itself. A channel only has one delay, so it aligns the voice to
+    /// the nearest main speaker. `None` if there is no main system.
+    pub fn delay(&self, point: &StagePoint) -> Option<f32> {
+        self.main_speakers()
+            .map(|speaker| speaker.location.distance(point) / SPEED_OF_SOUND * 1000.0)
+            .min_by(f32::total_cmp)
+    }
+
+    /// Send levels for a voice at `point` to every speaker fed by a send. The fill closest to
+    /// the voice gets unity, the others are attenuated by the inverse distance law, so the
+    /// fills nearest to the actor carry the voice.
+    ///
+    /// Speakers are not delayed individually: the console has no delay per send, and the delay
+    /// of a bus or matrix is shared by every actor on it. Fills are expected to be aligned to
+    /// the main system in their output processing.
+    pub fn send_levels(&self, point: &StagePoint) -> Vec<(WingSendTarget, Decibel)> {
+        let distance =
+            |speaker: &Speaker| speaker.location.distance(point).max(MIN_SPEAKER_DISTANCE);
+
+        let fills = || self.speakers.iter().filter(|speaker| !speaker.is_main());
+
+        let Some(closest) = fills().map(distance).min_by(f32::total_cmp) else {
+            return Vec::new();
+        };
+
+        fills()
+            .filter_map(|speaker| {
+                let target = speaker.send?;
+                let level = Decibel::from_amplitude(closest / distance(speaker));
+                Some((target, level))
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, serde::Deserialize, serde::Serialize, specta::Type, tauri_specta::Event)]
+pub enum StageEvent {
+    Updated(Stage),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wing::{id::WingId, WingMatrixId};
+
+    fn speaker(x: f32, y: f32, send: Option<WingSendTarget>) -> Speaker {
+        Speaker {
+            name: String::new(),
+            location: StagePoint { x, y },
+            send,
+        }
+    }
+
+    fn matrix(id: u8) -> WingSendTarget {
+        WingMatrixId::new(id).unwrap().into()
+    }
+
+    /// A 10 m stage with mains 1 m outside of it and two front fills in the house.
+    fn stage() -> Stage {
+        Stage {
+            width: 10.0,
+            depth: 8.0,
+            speakers: vec![
+                speaker(-1.0, 0.0, None),
+                speaker(11.0, 0.0, None),
+                speaker(2.0, -1.0, Some(matrix(1))),
+                speaker(8.0, -1.0, Some(matrix(2))),
+            ],
+        }
+    }
+
+    #[test]
+    fn pans_between_main_speakers() {
+        let stage = stage();
+        assert_eq!(stage.pan(&StagePoint { x: -1.0, y: 3.0 }).as_f32(), 0.0);
+        assert_eq!(stage.pan(&StagePoint { x: 5.0, y: 3.0 }).as_f32(), 0.5);
+        assert_eq!(stage.pan(&StagePoint { x: 8.0, y: 3.0 }).as_f32(), 0.75);
+    }
+
+    #[test]
+    fn pans_across_stage_without_main_speakers() {
+        let stage = Stage::default();
+        assert_eq!(stage.pan(&StagePoint { x: 2.5, y: 0.0 }).as_f32(), 0.25);
+    }
+
+    #[test]
+    fn nearest_fill_gets_unity_behind_a_main_speaker() {
+        // Main L is closer than both fills
+        let levels = stage().send_levels(&StagePoint { x: -1.0, y: 0.5 });
+        assert_eq!(levels[0], (matrix(1), Decibel::UNITY));
+        assert!(levels[1].1.as_f32() < 0.0);
+    }
+
+    #[test]
+    fn delays_to_nearest_main_speaker() {
+        let delay = stage().delay(&StagePoint { x: 11.0, y: 3.43 }).unwrap();
+        assert!((delay - 10.0).abs() < 1e-3);
+        assert_eq!(Stage::default().delay(&StagePoint::default()), None);
+    }
+
+    #[test]
+    fn nearest_fill_gets_unity() {
+        let levels = stage().send_levels(&StagePoint { x: 2.0, y: 1.0 });
+        assert_eq!(levels.len(), 2);
+
+        assert_eq!(levels[0], (matrix(1), Decibel::UNITY));
+        assert_eq!(levels[1].0, matrix(2));
+        // 2 m to the nearest speaker, sqrt(40) m to this one
+        let expected = Decibel::from_amplitude(2.0 / 40.0_f32.sqrt());
+        assert!((levels[1].1.as_f32() - expected.as_f32()).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,103 @@
+use itertools::Itertools;
+
+use crate::{
+    mix::{error::MixError, ActorId, MixConfig},
+    wing::{wing_name, MixerBackend, WingColor, WingStripId},
+    AppData, MutableState,
+};
+
+/// How syncing an actor would change its scribble strip.
+#[derive(Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ActorSyncChange {
+    pub actor_id: ActorId,
+    pub channel: WingStripId,
+    pub current_name: String,
+    pub current_color: WingColor,
+    /// The new name (already cut to the console's limit), `None` if it stays the same
+    pub name: Option<String>,
+    /// The new colour, `None` if it stays the same
+    pub color: Option<WingColor>,
+}
+
+/// Compares every actor with its strip and lists the strips that would change.
+async fn actor_sync_changes(
+    config: &MixConfig,
+    wing: &dyn MixerBackend,
+) -> Result<Vec<ActorSyncChange>, MixError> {
+    let mut changes = Vec::new();
+    for (actor_id, actor) in config.actors().sorted_by_key(|(id, _)| **id) {
+        let info = wing.channel(*actor.channel()).get_info().await?;
+
+        let name = Some(wing_name(actor.name())).filter(|name| *name != info.name);
+        let color = actor.color().filter(|color| *color != info.color);
+        if name.is_none() && color.is_none() {
+            continue;
+        }
+
+        changes.push(ActorSyncChange {
+            actor_id: *actor_id,
+            channel: *actor.channel(),
+            current_name: info.name,
+            current_color: info.color,
+            name,
+            color,
+        });
+    }
+
+    Ok(changes)
+}
+
+/// Lists what [`sync_actors_to_console`] would overwrite on the console.
+#[tauri::command]
+#[specta::specta]
+pub async fn preview_actor_sync(
+    state: MutableState<'_, AppData>,
+) -> Result<Vec<ActorSyncChange>, String> {
+    let app_data = state.read().await;
+    let Some(console) = app_data.console.as_ref() else {
+        return Err("Console not connected".to_string());
+    };
+
+    actor_sync_changes(&app_data.show.mix_config, console.as_ref())
+        .await
+        .map_err(|err| format!("Failed to compare actors: {}", err))
+}
+
+/// Writes every actor's name and colour to its scribble strip. Returns the number of strips
+/// that were changed.
+#[tauri::command]
+#[specta::specta]
+pub async fn sync_actors_to_console(state: MutableState<'_, AppData>) -> Result<usize, String> {
+    let app_data = state.read().await;
+    let Some(console) = app_data.console.as_ref() else {
+        return Err("Console not connected".to_string());
+    };
+
+    let changes = actor_sync_changes(&app_data.show.mix_config, console.as_ref())
+        .await
+        .map_err(|err| format!("Failed to compare actors: {}", err))?;
+
+    let transaction = console.transaction();
+    let backend: &dyn MixerBackend = &transaction;
+    for change in &changes {
+        let channel = backend.channel(change.channel);
+        if let Some(name) = &change.name {
+            channel
+                .set_name(name)
+                .await
+                .map_err(|err| format!("Failed to set name: {}", err))?;
+        }
+        if let Some(color) = change.color {
+            channel
+                .set_color(color)
+                .await
+                .map_err(|err| format!("Failed to set color: {}", err))?;
+        }
+    }
+    transaction
+        .commit()
+        .map_err(|err| format!("Failed to sync actors: {}", err))?;
+
+    Ok(changes.len())
+}
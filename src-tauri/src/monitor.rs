@@ -0,0 +1,40 @@
+use std::sync::Arc;
+
+use tauri::AppHandle;
+use tauri_specta::Event;
+
+use crate::{
+    wing::{WingTraffic, WingTrafficListener},
+    AppData, MutableState,
+};
+
+/// Mirrors a parameter sent to or received from the console. Only emitted while the network
+/// monitor is enabled.
+#[derive(Clone, serde::Serialize, serde::Deserialize, specta::Type, tauri_specta::Event)]
+pub struct ConsoleTrafficEvent(WingTraffic);
+
+pub fn traffic_listener(handle: &AppHandle) -> WingTrafficListener {
+    let handle = handle.clone();
+    Arc::new(move |traffic| {
+        let _ = ConsoleTrafficEvent(traffic)
+            .emit(&handle)
+            .inspect_err(|err| log::error!("Failed to emit console traffic event: {}", err));
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_network_monitor(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut app_data = state.write().await;
+
+    app_data.network_monitor = enabled;
+    if let Some(console) = app_data.console.as_ref() {
+        console.set_traffic_listener(enabled.then(|| traffic_listener(&handle)));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,114 @@
+use std::{fs::File, path::PathBuf, time::Duration};
+
+use tauri::{AppHandle, Manager};
+
+use crate::{
+    connection::DEFAULT_CONSOLE_ADDRESS, meter::DEFAULT_METER_FRAME_RATE, wing::WingRequestPolicy,
+    AppData, MutableState,
+};
+
+const PREFERENCES_FILE_NAME: &str = "preferences.json";
+
+#[derive(
+    Copy, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type,
+)]
+pub enum ConsoleBackendKind {
+    #[default]
+    Wing,
+    Simulated,
+}
+
+/// Per-machine settings that are not part of a show file.
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Preferences {
+    pub console_address: Option<String>,
+    pub console_backend: ConsoleBackendKind,
+    /// How often meter levels are sent to the UI, in frames per second
+    pub meter_frame_rate: Option<u32>,
+    /// How long to wait for a reply to a console request, in ms
+    pub request_timeout_ms: Option<u32>,
+    /// How often a console request is resent after it timed out
+    pub request_retries: Option<u32>,
+}
+
+impl Preferences {
+    fn path(handle: &AppHandle) -> Result<PathBuf, String> {
+        handle
+            .path()
+            .app_config_dir()
+            .map(|dir| dir.join(PREFERENCES_FILE_NAME))
+            .map_err(|err| format!("Failed to resolve config directory: {}", err))
+    }
+
+    /// Loads the preferences, falling back to the defaults if none have been saved yet.
+    pub fn load(handle: &AppHandle) -> Result<Self, String> {
+        let path = Self::path(handle)?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let file = File::open(&path).map_err(|err| format!("Failed to open file: {}", err))?;
+        serde_json::from_reader(file).map_err(|err| format!("Failed to parse preferences: {}", err))
+    }
+
+    pub fn save(&self, handle: &AppHandle) -> Result<(), String> {
+        let path = Self::path(handle)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|err| format!("Failed to create config directory: {}", err))?;
+        }
+
+        let file = File::create(&path).map_err(|err| format!("Failed to open file: {}", err))?;
+        serde_json::to_writer_pretty(&file, self)
+            .map_err(|err| format!("Failed to write preferences: {}", err))
+    }
+
+    pub fn console_address(&self) -> &str {
+        self.console_address
+            .as_deref()
+            .unwrap_or(DEFAULT_CONSOLE_ADDRESS)
+    }
+
+    pub fn meter_frame_rate(&self) -> u32 {
+        self.meter_frame_rate.unwrap_or(DEFAULT_METER_FRAME_RATE)
+    }
+
+    pub fn request_policy(&self) -> WingRequestPolicy {
+        let default = WingRequestPolicy::default();
+        WingRequestPolicy {
+            timeout: self
+                .request_timeout_ms
+                .map(|timeout| Duration::from_millis(timeout.into()))
+                .unwrap_or(default.timeout),
+            retries: self.request_retries.unwrap_or(default.retries),
+        }
+    }
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_preferences(state: MutableState<'_, AppData>) -> Result<Preferences, String> {
+    Ok(state.read().await.preferences.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_policy_falls_back_to_the_default() {
+        let mut preferences = Preferences::default();
+        let default = WingRequestPolicy::default();
+        assert_eq!(preferences.request_policy().timeout, default.timeout);
+        assert_eq!(preferences.request_policy().retries, default.retries);
+
+        preferences.request_timeout_ms = Some(1500);
+        preferences.request_retries = Some(0);
+        assert_eq!(
+            preferences.request_policy().timeout,
+            Duration::from_millis(1500)
+        );
+        assert_eq!(preferences.request_policy().retries, 0);
+    }
+}
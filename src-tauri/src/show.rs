@@ -5,8 +5,10 @@ use tauri_plugin_dialog::DialogExt;
 use tauri_specta::Event;
 
 use crate::{
-    cue::{Cue, CueExecutionContext, CueId, CueList, SingleDcaAssignment},
-    mix::MixConfig,
+    cue::{Cue, CueExecutionContext, CueId, CueList, FxSlotState, SingleDcaAssignment},
+    mix::{ActorId, MixConfig, PositionId},
+    utils::Decibel,
+    wing::WingFxSlotId,
     AppData, MutableState,
 };
 
@@ -40,28 +42,59 @@ pub async fn goto_cue(
     state: MutableState<'_, AppData>,
     cue_id: CueId,
 ) -> Result<(), String> {
-    let mut app_data = state.write().await;
+    // The cue runs without holding the state, so that the show stays usable during a fade
+    let task = {
+        let mut app_data = state.write().await;
 
-    let Some(console) = app_data.console.as_ref() else {
-        return Err("Console not connected".to_string());
-    };
+        let Some(console) = app_data.console.clone() else {
+            return Err("Console not connected".to_string());
+        };
 
-    let Some(cue) = app_data.show.cues.get(&cue_id) else {
-        return Err("Cue not found".to_string());
+        let Some(cue) = app_data.show.cues.get(&cue_id).cloned() else {
+            return Err("Cue not found".to_string());
+        };
+        let config = app_data.show.mix_config.clone();
+
+        // A new GO takes over from a cue that is still fading
+        if let Some(running) = app_data.running_cue.take() {
+            running.abort();
+        }
+
+        let task = tokio::spawn(async move {
+            cue.activate(CueExecutionContext {
+                config: &config,
+                wing: console.as_ref(),
+            })
+            .await
+        });
+        app_data.running_cue = Some(task.abort_handle());
+
+        app_data
+            .show_state
+            .update_current_cue(Some(cue_id), &handle);
+
+        task
     };
 
-    cue.activate(CueExecutionContext {
-        config: &app_data.show.mix_config,
-        wing: console,
-    })
-    .await
-    .map_err(|err| format!("Failed to activate cue: {}", err))?;
+    match task.await {
+        Ok(result) => result.map_err(|err| format!("Failed to activate cue: {}", err)),
+        Err(err) if err.is_cancelled() => Ok(()),
+        Err(err) => Err(format!("Failed to activate cue: {}", err)),
+    }
+}
 
-    app_data
-        .show_state
-        .update_current_cue(Some(cue_id), &handle);
+/// Re-applies the current cue, e.g. after the console has been reconnected.
+#[tauri::command]
+#[specta::specta]
+pub async fn reapply_current_cue(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+) -> Result<(), String> {
+    let Some(cue_id) = state.read().await.show_state.current_cue_id else {
+        return Err("No cue is active".to_string());
+    };
 
-    Ok(())
+    goto_cue(handle, state, cue_id).await
 }
 
 #[derive(Clone, Default, serde::Serialize, serde::Deserialize, specta::Type)]
@@ -176,7 +209,7 @@ pub async fn new_show(handle: AppHandle) -> Result<(), String> {
 
     let _ = ShowEvent::Loaded(show)
         .emit(&handle)
-        .inspect_err(|err| println!("Failed to send showfile load event: {}", err));
+        .inspect_err(|err| log::error!("Failed to send showfile load event: {}", err));
 
     Ok(())
 }
@@ -217,7 +250,7 @@ pub async fn open_show(handle: AppHandle) -> Result<(), String> {
 
     let _ = ShowEvent::Loaded(show)
         .emit(&handle)
-        .inspect_err(|err| println!("Failed to send showfile load event: {}", err));
+        .inspect_err(|err| log::error!("Failed to send showfile load event: {}", err));
 
     Ok(())
 }
@@ -271,6 +304,154 @@ pub async fn set_cue_dca_assignment(
     Ok(())
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn set_cue_position_assignment(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    cue_id: CueId,
+    actor_id: ActorId,
+    position_id: Option<PositionId>,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+
+    app_state
+        .show
+        .mix_config
+        .actor(actor_id)
+        .map_err(|err| format!("Failed to set position: {}", err))?;
+    if let Some(position_id) = position_id {
+        app_state
+            .show
+            .mix_config
+            .position(position_id)
+            .map_err(|err| format!("Failed to set position: {}", err))?;
+    }
+
+    let Some(cue) = app_state.show.cues.get_mut(&cue_id) else {
+        return Err("Cue not found".to_string());
+    };
+
+    cue.set_position_assignment(actor_id, position_id);
+
+    let _ = ShowEvent::CueUpdated(cue.clone()).emit(&handle);
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_cue_dca_level(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    cue_id: CueId,
+    dca_idx: u8,
+    level: Option<Decibel>,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+
+    let Some(cue) = app_state.show.cues.get_mut(&cue_id) else {
+        return Err("Cue not found".to_string());
+    };
+
+    cue.set_dca_level(dca_idx, level)
+        .map_err(|err| format!("Failed to set DCA level: {}", err))?;
+
+    let _ = ShowEvent::CueUpdated(cue.clone()).emit(&handle);
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_cue_dca_mute(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    cue_id: CueId,
+    dca_idx: u8,
+    mute: Option<bool>,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+
+    let Some(cue) = app_state.show.cues.get_mut(&cue_id) else {
+        return Err("Cue not found".to_string());
+    };
+
+    cue.set_dca_mute(dca_idx, mute)
+        .map_err(|err| format!("Failed to set DCA mute: {}", err))?;
+
+    let _ = ShowEvent::CueUpdated(cue.clone()).emit(&handle);
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_cue_mute_group(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    cue_id: CueId,
+    mute_group_idx: u8,
+    mute_group_state: Option<bool>,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+
+    let Some(cue) = app_state.show.cues.get_mut(&cue_id) else {
+        return Err("Cue not found".to_string());
+    };
+
+    cue.set_mute_group_state(mute_group_idx, mute_group_state)
+        .map_err(|err| format!("Failed to set mute group: {}", err))?;
+
+    let _ = ShowEvent::CueUpdated(cue.clone()).emit(&handle);
+
+    Ok(())
+}
+
+/// Sets the state a cue puts an FX slot in. `None` leaves the slot untouched by the cue.
+#[tauri::command]
+#[specta::specta]
+pub async fn set_cue_fx_slot(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    cue_id: CueId,
+    slot: WingFxSlotId,
+    slot_state: Option<FxSlotState>,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+
+    let Some(cue) = app_state.show.cues.get_mut(&cue_id) else {
+        return Err("Cue not found".to_string());
+    };
+
+    cue.set_fx_slot_state(slot, slot_state);
+
+    let _ = ShowEvent::CueUpdated(cue.clone()).emit(&handle);
+
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_cue_fade_time(
+    handle: AppHandle,
+    state: MutableState<'_, AppData>,
+    cue_id: CueId,
+    fade_time: f32,
+) -> Result<(), String> {
+    let mut app_state = state.write().await;
+
+    let Some(cue) = app_state.show.cues.get_mut(&cue_id) else {
+        return Err("Cue not found".to_string());
+    };
+
+    cue.set_fade_time(fade_time);
+
+    let _ = ShowEvent::CueUpdated(cue.clone()).emit(&handle);
+
+    Ok(())
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn rename_cue(
@@ -0,0 +1,151 @@
+use std::{
+    fmt, num,
+    ops::{Add, Sub},
+    str,
+};
+
+/// A level in decibels.
+///
+/// Silence (-inf dB) is represented by [`Decibel::NEG_INFINITY`], which is the lowest level the
+/// console can represent. All values below it are treated as -inf, which also keeps the value
+/// representable in JSON.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    PartialOrd,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+    specta::Type,
+)]
+#[serde(transparent)]
+pub struct Decibel(f32);
+
+impl Decibel {
+    /// Levels at or below this value are -inf.
+    pub const MIN: f32 = -144.0;
+
+    /// Silence.
+    pub const NEG_INFINITY: Self = Self(Self::MIN);
+
+    /// Unity gain (0 dB).
+    pub const UNITY: Self = Self(0.0);
+
+    /// Creates a new Decibel value.
+    ///
+    /// Values at or below [`Decibel::MIN`] (including `f32::NEG_INFINITY`) and NaN become -inf.
+    #[inline]
+    pub fn new(value: f32) -> Self {
+        if value.is_nan() || value <= Self::MIN {
+            Self::NEG_INFINITY
+        } else {
+            Self(value)
+        }
+    }
+
+    /// Returns the level in dB, [`Decibel::MIN`] for -inf.
+    #[inline]
+    pub fn as_f32(self) -> f32 {
+        self.0
+    }
+
+    #[inline]
+    pub fn is_neg_infinity(self) -> bool {
+        self.0 <= Self::MIN
+    }
+
+    /// Clamps the level to the given range in dB. -inf stays -inf.
+    #[inline]
+    pub fn clamp(self, min: f32, max: f32) -> Self {
+        if self.is_neg_infinity() {
+            self
+        } else {
+            Self::new(self.0.clamp(min, max))
+        }
+    }
+
+    /// Converts the level to a linear amplitude factor, 0.0 for -inf.
+    #[inline]
+    pub fn to_amplitude(self) -> f32 {
+        if self.is_neg_infinity() {
+            0.0
+        } else {
+            10f32.powf(self.0 / 20.0)
+        }
+    }
+
+    /// Creates a level from a linear amplitude factor. Factors of 0.0 or less are -inf.
+    #[inline]
+    pub fn from_amplitude(amplitude: f32) -> Self {
+        if amplitude <= 0.0 {
+            Self::NEG_INFINITY
+        } else {
+            Self::new(20.0 * amplitude.log10())
+        }
+    }
+}
+
+impl fmt::Display for Decibel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_neg_infinity() {
+            write!(f, "-inf dB")
+        } else {
+            write!(f, "{:.1} dB", self.0)
+        }
+    }
+}
+
+impl From<f32> for Decibel {
+    fn from(value: f32) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<Decibel> for f32 {
+    fn from(value: Decibel) -> Self {
+        value.0
+    }
+}
+
+impl str::FromStr for Decibel {
+    type Err = num::ParseFloatError;
+
+    /// Parses a plain number of dB, optionally followed by `dB`. `-inf` and `-oo` are silence.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let s = s
+            .strip_suffix("dB")
+            .or_else(|| s.strip_suffix("db"))
+            .unwrap_or(s)
+            .trim();
+
+        match s {
+            "-inf" | "-oo" => Ok(Self::NEG_INFINITY),
+            _ => Ok(Self::new(s.parse()?)),
+        }
+    }
+}
+
+impl Add<f32> for Decibel {
+    type Output = Decibel;
+
+    /// Adds a gain in dB. -inf stays -inf.
+    fn add(self, rhs: f32) -> Self::Output {
+        if self.is_neg_infinity() {
+            self
+        } else {
+            Self::new(self.0 + rhs)
+        }
+    }
+}
+
+impl Sub<f32> for Decibel {
+    type Output = Decibel;
+
+    /// Subtracts a gain in dB. -inf stays -inf.
+    fn sub(self, rhs: f32) -> Self::Output {
+        self + -rhs
+    }
+}
@@ -1,2 +1,5 @@
 mod clamped;
 pub use clamped::*;
+
+mod decibel;
+pub use decibel::*;
@@ -0,0 +1,66 @@
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use libwing::WingNodeDef;
+
+use crate::wing::{
+    error::WingError, CachedValue, WingChannel, WingDca, WingDcaId, WingFxSlot, WingFxSlotId,
+    WingMuteGroup, WingMuteGroupId, WingStripId, WingTransaction, WingValue,
+};
+
+pub type BackendFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, WingError>> + Send + 'a>>;
+
+/// Node level access to a console.
+///
+/// All wrappers in this module ([`WingChannel`], [`WingDca`], ...) are built on top of this
+/// trait, so they work the same against the real [`crate::wing::Wing`] and the
+/// [`crate::wing::SimulatedWing`].
+pub trait MixerBackend: Send + Sync {
+    fn request_string(&self, node_id: i32) -> BackendFuture<'_, String>;
+    fn request_int(&self, node_id: i32) -> BackendFuture<'_, i32>;
+    fn request_float(&self, node_id: i32) -> BackendFuture<'_, f32>;
+
+    /// Requests the definitions of all direct children of a node.
+    fn request_node_children(&self, node_id: i32) -> BackendFuture<'_, Vec<Arc<WingNodeDef>>>;
+
+    fn set_string(&self, node_id: i32, value: &str) -> Result<(), WingError>;
+    fn set_int(&self, node_id: i32, value: i32) -> Result<(), WingError>;
+    fn set_float(&self, node_id: i32, value: f32) -> Result<(), WingError>;
+
+    /// The last known value of a node, without asking the console.
+    fn cached_value(&self, node_id: i32) -> Option<CachedValue>;
+
+    /// Sends several values at once, as a single burst if the backend supports it.
+    fn write_batch(&self, writes: Vec<(i32, WingValue)>) -> Result<(), WingError>;
+
+    /// Registers a listener that is called for every value sent to or received from the
+    /// console. `None` removes the current listener.
+    fn set_traffic_listener(&self, listener: Option<WingTrafficListener>);
+
+    /// Checks whether the console still answers requests.
+    fn ping(&self) -> BackendFuture<'_, ()>;
+
+    /// Releases the console. Pending and future requests on this backend will fail.
+    fn close(&self);
+}
+
+impl dyn MixerBackend + '_ {
+    pub fn channel(&self, channel: impl Into<WingStripId>) -> WingChannel<'_> {
+        WingChannel::new(self, channel)
+    }
+
+    pub fn dca(&self, dca_id: WingDcaId) -> WingDca<'_> {
+        WingDca::new(self, dca_id)
+    }
+
+    pub fn mute_group(&self, mute_group_id: WingMuteGroupId) -> WingMuteGroup<'_> {
+        WingMuteGroup::new(self, mute_group_id)
+    }
+
+    pub fn fx_slot(&self, slot_id: WingFxSlotId) -> WingFxSlot<'_> {
+        WingFxSlot::new(self, slot_id)
+    }
+
+    pub fn transaction(&self) -> WingTransaction<'_> {
+        WingTransaction::new(self)
+    }
+}
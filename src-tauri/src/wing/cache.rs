@@ -0,0 +1,104 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use libwing::WingNodeData;
+
+use crate::wing::WingValue;
+
+/// Floats closer than this are considered equal when diffing against the cache.
+const FLOAT_TOLERANCE: f32 = 1e-4;
+
+/// A node value known to musicalix, either pushed by the console or written by us.
+#[derive(Clone)]
+pub enum CachedValue {
+    Received(Arc<WingNodeData>),
+    Written(WingValue),
+}
+
+impl CachedValue {
+    pub fn as_string(&self) -> String {
+        match self {
+            Self::Received(data) => data.get_string(),
+            Self::Written(value) => value.as_string(),
+        }
+    }
+
+    pub fn as_int(&self) -> i32 {
+        match self {
+            Self::Received(data) => data.get_int(),
+            Self::Written(value) => value.as_int(),
+        }
+    }
+
+    pub fn as_float(&self) -> f32 {
+        match self {
+            Self::Received(data) => data.get_float(),
+            Self::Written(value) => value.as_float(),
+        }
+    }
+
+    /// Whether writing `value` would leave this node unchanged.
+    pub fn matches(&self, value: &WingValue) -> bool {
+        match value {
+            WingValue::String(value) => &self.as_string() == value,
+            WingValue::Int(value) => self.as_int() == *value,
+            WingValue::Float(value) => (self.as_float() - value).abs() <= FLOAT_TOLERANCE,
+        }
+    }
+}
+
+struct CacheEntry {
+    value: CachedValue,
+    updated_at: Instant,
+}
+
+/// Local mirror of the console state, keyed by node ID.
+///
+/// The console pushes a notification for every parameter that changes, so an entry stays
+/// valid until it is overwritten. Entries older than `max_age` are still treated as stale,
+/// in case a notification got lost on the way.
+pub struct WingStateCache {
+    nodes: HashMap<i32, CacheEntry>,
+    max_age: Duration,
+}
+
+impl Default for WingStateCache {
+    fn default() -> Self {
+        Self {
+            nodes: HashMap::new(),
+            max_age: Duration::from_secs(60),
+        }
+    }
+}
+
+impl WingStateCache {
+    pub fn set_max_age(&mut self, max_age: Duration) {
+        self.max_age = max_age;
+    }
+
+    pub fn update(&mut self, node_id: i32, value: CachedValue) {
+        self.nodes.insert(
+            node_id,
+            CacheEntry {
+                value,
+                updated_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Forgets the value of `node_id`, so that it is requested from the console again.
+    pub fn invalidate(&mut self, node_id: i32) {
+        self.nodes.remove(&node_id);
+    }
+
+    /// Returns the cached value of `node_id` if it is still fresh.
+    pub fn get(&self, node_id: i32) -> Option<CachedValue> {
+        self.nodes
+            .get(&node_id)
+            .filter(|entry| entry.updated_at.elapsed() <= self.max_age)
+            .map(|entry| entry.value.clone())
+    }
+}
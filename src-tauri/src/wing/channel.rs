@@ -3,7 +3,21 @@ use std::{collections::HashSet, fmt::Debug};
 use itertools::Itertools;
 use libwing::WingConsole;
 
-use crate::wing::{error::WingError, id::WingId, Wing, WingColor, WingDcaId};
+use crate::{
+    utils::Decibel,
+    wing::{
+        db_to_fader, error::WingError, fader_to_db, id::WingId, node_id_from_path, MixerBackend,
+        WingColor, WingDcaId, WingInputSource, WingMuteGroupId, WingStripId, WingValue,
+    },
+};
+
+/// Maximum number of characters the console accepts for a name.
+pub const WING_NAME_MAX_LENGTH: usize = 16;
+
+/// Cuts a name to what the console can store.
+pub fn wing_name(name: &str) -> String {
+    name.chars().take(WING_NAME_MAX_LENGTH).collect()
+}
 
 #[derive(serde::Serialize, serde::Deserialize, specta::Type)]
 pub struct WingChannelInfo {
@@ -54,39 +68,73 @@ impl TryFrom<u8> for WingChannelId {
     }
 }
 
+/// A strip on the console. Despite the name, this may be any [`WingStripId`], not only an
+/// input channel.
 pub struct WingChannel<'a> {
-    wing: &'a Wing,
-    id: WingChannelId,
+    wing: &'a dyn MixerBackend,
+    id: WingStripId,
 }
 
 impl<'a> WingChannel<'a> {
-    pub fn new(wing: &'a Wing, id: WingChannelId) -> Self {
-        Self { wing, id }
+    pub fn new(wing: &'a dyn MixerBackend, id: impl Into<WingStripId>) -> Self {
+        Self {
+            wing,
+            id: id.into(),
+        }
+    }
+
+    pub fn id(&self) -> WingStripId {
+        self.id
     }
 }
 
-// DCAs
+// Typed access to properties, used by the processing blocks (EQ, dynamics, ...)
 impl<'a> WingChannel<'a> {
-    fn get_channel_property(&self, property: &str) -> Option<i32> {
-        let name = format!("/ch/{}/{}", self.id.display(), property);
-        WingConsole::name_to_id(&name)
+    pub(super) fn backend(&self) -> &'a dyn MixerBackend {
+        self.wing
     }
 
-    pub async fn get_tags(&self) -> Result<WingChannelTagList, WingError> {
-        let tags = self
-            .wing
-            .request_string(self.get_channel_property("tags").unwrap())
-            .await?;
+    fn property_id(&self, property: &str) -> Result<i32, WingError> {
+        let name = format!("{}/{}", self.id.path(), property);
+        WingConsole::name_to_id(&name).ok_or(WingError::UnknownNodePath(name))
+    }
+
+    pub(super) async fn get_float(&self, property: &str) -> Result<f32, WingError> {
+        self.wing.request_float(self.property_id(property)?).await
+    }
+
+    pub(super) async fn get_bool(&self, property: &str) -> Result<bool, WingError> {
+        Ok(self.wing.request_int(self.property_id(property)?).await? != 0)
+    }
+
+    pub(super) async fn get_string(&self, property: &str) -> Result<String, WingError> {
+        self.wing.request_string(self.property_id(property)?).await
+    }
+
+    pub(super) fn set_value(&self, property: &str, value: &WingValue) -> Result<(), WingError> {
+        self.set_node(self.property_id(property)?, value)
+    }
+
+    fn set_node(&self, node_id: i32, value: &WingValue) -> Result<(), WingError> {
+        match value {
+            WingValue::String(value) => self.wing.set_string(node_id, value),
+            WingValue::Int(value) => self.wing.set_int(node_id, *value),
+            WingValue::Float(value) => self.wing.set_float(node_id, *value),
+        }
+    }
+}
 
-        let tags = WingChannelTagList::new(tags.split(",").map(|tag| tag.to_string()));
+// DCAs
+impl<'a> WingChannel<'a> {
+    pub async fn get_tags(&self) -> Result<WingChannelTagList, WingError> {
+        let tags = self.get_string("tags").await?;
 
-        Ok(tags)
+        Ok(WingChannelTagList::parse(&tags))
     }
 
     pub fn set_tags(&self, tags: WingChannelTagList) -> Result<(), WingError> {
-        let data = tags.tags().into_iter().join(",");
-        self.wing
-            .set_string(self.get_channel_property("tags").unwrap(), &data)?;
+        let data = tags.to_value();
+        self.wing.set_string(self.property_id("tags")?, &data)?;
         Ok(())
     }
 
@@ -114,56 +162,59 @@ impl<'a> WingChannel<'a> {
         Ok(())
     }
 
+    pub async fn set_mute_groups(
+        &self,
+        mute_groups: impl IntoIterator<Item = WingMuteGroupId>,
+    ) -> Result<(), WingError> {
+        let mut tags = self.get_tags().await?;
+        tags.set_mute_groups(mute_groups);
+        self.set_tags(tags)?;
+        Ok(())
+    }
+
     pub fn mute(&self) -> Result<(), WingError> {
-        self.wing
-            .set_int(self.get_channel_property("mute").unwrap(), 1)
+        self.wing.set_int(self.property_id("mute")?, 1)
     }
 
     pub fn unmute(&self) -> Result<(), WingError> {
-        self.wing
-            .set_int(self.get_channel_property("mute").unwrap(), 0)
+        self.wing.set_int(self.property_id("mute")?, 0)
+    }
+
+    pub async fn get_fader_level(&self) -> Result<Decibel, WingError> {
+        Ok(fader_to_db(self.get_float("fdr").await?))
+    }
+
+    pub fn set_fader_level(&self, level: Decibel) -> Result<(), WingError> {
+        self.set_value("fdr", &WingValue::Float(db_to_fader(level)))
     }
 
     /// Pan is in the range 0.0..=1.0, 0.5 is center
     pub fn set_pan(&self, pan: f32) -> Result<(), WingError> {
         // map pan to range -100 to 100
         let pan = (pan - 0.5) * 200.0;
-        self.wing
-            .set_int(self.get_channel_property("pan").unwrap(), pan as i32)?;
+        self.wing.set_int(self.property_id("pan")?, pan as i32)?;
 
         Ok(())
     }
 
     pub async fn get_name(&self, source_linked: bool) -> Result<String, WingError> {
-        let name = self
-            .wing
-            .request_string(
-                self.get_channel_property(if source_linked { "$name" } else { "name" })
-                    .unwrap(),
-            )
-            .await?;
-        Ok(name)
+        self.get_string(if source_linked { "$name" } else { "name" })
+            .await
     }
 
     pub async fn get_color(&self, source_linked: bool) -> Result<WingColor, WingError> {
-        let int_data = self
-            .wing
-            .request_int(
-                self.get_channel_property(if source_linked { "$col" } else { "col" })
-                    .unwrap(),
-            )
-            .await? as u8;
+        let property = if source_linked { "$col" } else { "col" };
+        let int_data = self.wing.request_int(self.property_id(property)?).await?;
 
-        Ok(int_data.try_into().unwrap())
+        WingColor::try_from(int_data as u8).map_err(|_| WingError::InvalidColor(int_data))
     }
 
     pub async fn is_source_linked(&self) -> Result<bool, WingError> {
-        let int_data = self
-            .wing
-            .request_int(self.get_channel_property("clink").unwrap())
-            .await?;
+        if !self.id.kind().has_source() {
+            return Ok(false);
+        }
 
-        Ok(int_data != 0)
+        self.get_bool("clink").await
     }
 
     pub async fn get_info(&self) -> Result<WingChannelInfo, WingError> {
@@ -174,6 +225,40 @@ impl<'a> WingChannel<'a> {
 
         Ok(WingChannelInfo { name, color })
     }
+
+    /// The input whose name and colour the strip shows, if it is source linked.
+    async fn linked_source(&self) -> Result<Option<WingInputSource>, WingError> {
+        if !self.is_source_linked().await? {
+            return Ok(None);
+        }
+
+        self.get_input_source().await
+    }
+
+    /// Writes a property that is shown on the scribble strip. For source linked strips the
+    /// property of the source is written instead, as that is what the console displays.
+    async fn set_display_property(
+        &self,
+        property: &str,
+        value: &WingValue,
+    ) -> Result<(), WingError> {
+        let Some(source) = self.linked_source().await? else {
+            return self.set_value(property, value);
+        };
+
+        self.set_node(node_id_from_path(&source.node_path(property))?, value)
+    }
+
+    /// Names longer than [`WING_NAME_MAX_LENGTH`] are cut off.
+    pub async fn set_name(&self, name: &str) -> Result<(), WingError> {
+        self.set_display_property("name", &WingValue::String(wing_name(name)))
+            .await
+    }
+
+    pub async fn set_color(&self, color: WingColor) -> Result<(), WingError> {
+        self.set_display_property("col", &WingValue::Int(color as i32))
+            .await
+    }
 }
 
 pub struct WingChannelTagList {
@@ -183,10 +268,24 @@ pub struct WingChannelTagList {
 impl WingChannelTagList {
     pub fn new(tags: impl IntoIterator<Item = String>) -> Self {
         Self {
-            tags: tags.into_iter().collect(),
+            tags: tags
+                .into_iter()
+                .map(|tag| tag.trim().to_string())
+                .filter(|tag| !tag.is_empty())
+                .collect(),
         }
     }
 
+    /// Parses the comma separated tags of a channel. A channel without tags has an empty list.
+    pub fn parse(value: &str) -> Self {
+        Self::new(value.split(',').map(|tag| tag.to_string()))
+    }
+
+    /// The tags in the form the console normalises them to, sorted and comma separated.
+    pub fn to_value(&self) -> String {
+        self.tags.iter().sorted().join(",")
+    }
+
     fn dca_tag(dca_id: WingDcaId) -> String {
         format!("#D{}", dca_id.display())
     }
@@ -211,6 +310,30 @@ impl WingChannelTagList {
         }
     }
 
+    fn mute_group_tag(mute_group_id: WingMuteGroupId) -> String {
+        format!("#M{}", mute_group_id.display())
+    }
+
+    pub fn add_mute_group(&mut self, mute_group_id: WingMuteGroupId) {
+        self.tags.insert(Self::mute_group_tag(mute_group_id));
+    }
+
+    pub fn remove_mute_group(&mut self, mute_group_id: WingMuteGroupId) {
+        self.tags.remove(&Self::mute_group_tag(mute_group_id));
+    }
+
+    pub fn clear_mute_groups(&mut self) {
+        self.tags.retain(|tag| !tag.starts_with("#M"));
+    }
+
+    pub fn set_mute_groups(&mut self, mute_groups: impl IntoIterator<Item = WingMuteGroupId>) {
+        self.clear_mute_groups();
+
+        for mute_group_id in mute_groups {
+            self.add_mute_group(mute_group_id);
+        }
+    }
+
     pub fn tags(self) -> HashSet<String> {
         self.tags
     }
@@ -221,3 +344,87 @@ impl std::fmt::Display for WingChannelTagList {
         self.tags.fmt(f)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wing::{SimulatedWing, WingStripKind};
+
+    const STRIP_KINDS: [WingStripKind; 5] = [
+        WingStripKind::Channel,
+        WingStripKind::Aux,
+        WingStripKind::Bus,
+        WingStripKind::Main,
+        WingStripKind::Matrix,
+    ];
+
+    #[test]
+    fn property_paths_resolve_for_every_strip_kind() {
+        let wing = SimulatedWing::default();
+        let backend: &dyn MixerBackend = &wing;
+
+        for kind in STRIP_KINDS {
+            let mut properties = vec!["tags", "mute", "fdr", "pan", "name", "col"];
+            if kind.has_source() {
+                properties.extend([
+                    "clink",
+                    "$name",
+                    "$col",
+                    "in/set/trim",
+                    "in/conn/grp",
+                    "in/conn/in",
+                ]);
+            }
+
+            for number in kind.id_range() {
+                let channel = backend.channel(WingStripId::new(kind, number).unwrap());
+                for property in &properties {
+                    assert!(
+                        channel.property_id(property).is_ok(),
+                        "{}/{} does not resolve",
+                        channel.id().path(),
+                        property
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn untagged_channel_has_no_tags() {
+        assert!(WingChannelTagList::parse("").tags().is_empty());
+        assert_eq!(WingChannelTagList::parse("").to_value(), "");
+    }
+
+    #[test]
+    fn tags_are_written_sorted_without_empty_entries() {
+        let mut tags = WingChannelTagList::parse("#D3,,");
+        tags.add_dca(WingDcaId::new(1).unwrap());
+        assert_eq!(tags.to_value(), "#D1,#D3");
+    }
+
+    #[test]
+    fn assigning_untagged_channel_is_a_no_op_the_second_time() {
+        let wing = SimulatedWing::default();
+        let backend: &dyn MixerBackend = &wing;
+        let dca_id = WingDcaId::new(1).unwrap();
+
+        tauri::async_runtime::block_on(async {
+            for expected_writes in [1, 0] {
+                let transaction = backend.transaction();
+                let staged: &dyn MixerBackend = &transaction;
+                staged
+                    .channel(WingChannelId::new(1).unwrap())
+                    .set_dcas([dca_id])
+                    .await
+                    .unwrap();
+                assert_eq!(transaction.commit().unwrap(), expected_writes);
+            }
+        });
+
+        assert_eq!(
+            wing.value_at("/ch/1/tags"),
+            Some(WingValue::String("#D1".to_string()))
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::{
-    collections::HashMap,
-    sync::{mpsc, Arc, Mutex},
+    collections::{HashMap, VecDeque},
+    sync::{mpsc, Arc, Mutex, RwLock},
     thread,
     time::Duration,
 };
@@ -8,17 +8,72 @@ use std::{
 use libwing::{WingConsole, WingNodeData, WingNodeDef, WingResponse};
 use tokio::sync::oneshot;
 
-use crate::wing::{error::WingError, WingChannel, WingChannelId, WingDca, WingDcaId};
+use crate::wing::{
+    error::WingError, node_path, BackendFuture, CachedValue, MixerBackend, WingStateCache,
+    WingTraffic, WingTrafficDirection, WingTrafficListener, WingValue,
+};
 
 pub enum WingRequest {
     NodeData(i32, oneshot::Sender<Arc<WingNodeData>>),
     NodeDef(i32, oneshot::Sender<Arc<WingNodeDef>>),
+    NodeChildren(i32, oneshot::Sender<Vec<Arc<WingNodeDef>>>),
+    Set(i32, WingValue),
+    SetBatch(Vec<(i32, WingValue)>),
+    Shutdown,
+}
+
+/// Batched writes are sent in chunks of this size ...
+const WRITE_BATCH_CHUNK_SIZE: usize = 64;
+/// ... with this pause in between, so that the console is not flooded.
+const WRITE_BATCH_CHUNK_PAUSE: Duration = Duration::from_millis(2);
+
+/// Node that is requested to check whether the console is still responding.
+const HEALTH_CHECK_NODE: &str = "/ch/1/name";
+
+/// Deadline and retry behaviour for node requests sent to the console.
+#[derive(Debug, Copy, Clone)]
+pub struct WingRequestPolicy {
+    /// How long to wait for a single reply before giving up on it
+    pub timeout: Duration,
+    /// How often a request is resent after its first attempt timed out
+    pub retries: u32,
+}
+
+impl Default for WingRequestPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_millis(500),
+            retries: 2,
+        }
+    }
+}
+
+struct NodeChildrenRequest {
+    node_id: i32,
+    children: Vec<Arc<WingNodeDef>>,
+    sender: oneshot::Sender<Vec<Arc<WingNodeDef>>>,
+}
+
+/// What the definitions received since the last [`WingResponse::RequestEnd`] answered.
+#[derive(Copy, Clone)]
+enum PendingDefinitions {
+    /// A definition request for a single node
+    Node,
+    /// A children request for the node with this ID
+    Children(i32),
+    /// A request that has been given up on in the meantime
+    Abandoned,
 }
 
 #[derive(Default)]
 pub struct WingRequests {
     node_data_requests: HashMap<i32, Vec<oneshot::Sender<Arc<WingNodeData>>>>,
     node_def_requests: HashMap<i32, Vec<oneshot::Sender<Arc<WingNodeDef>>>>,
+    /// The console terminates the answer to each definition request with a
+    /// [`WingResponse::RequestEnd`] that does not say which request it ends. The children
+    /// request it belongs to is found by the parent ID of the definitions received before it.
+    node_children_requests: VecDeque<NodeChildrenRequest>,
+    pending_definitions: Option<PendingDefinitions>,
 }
 
 impl WingRequests {
@@ -36,194 +91,429 @@ impl WingRequests {
             .push(sender);
     }
 
+    pub fn request_node_children(
+        &mut self,
+        node_id: i32,
+        sender: oneshot::Sender<Vec<Arc<WingNodeDef>>>,
+    ) {
+        self.node_children_requests.push_back(NodeChildrenRequest {
+            node_id,
+            children: Vec::new(),
+            sender,
+        });
+    }
+
+    /// Drops senders whose requester has already given up (e.g. because the request timed out).
+    pub fn prune(&mut self) {
+        self.node_data_requests.retain(|_, requests| {
+            requests.retain(|req| !req.is_closed());
+            !requests.is_empty()
+        });
+        self.node_def_requests.retain(|_, requests| {
+            requests.retain(|req| !req.is_closed());
+            !requests.is_empty()
+        });
+        self.node_children_requests
+            .retain(|req| !req.sender.is_closed());
+    }
+
+    pub fn resolve_node_data(&mut self, node_id: i32, data: Arc<WingNodeData>) {
+        if let Some(requests) = self.node_data_requests.remove(&node_id) {
+            for req in requests {
+                let _ = req.send(data.clone());
+            }
+        }
+    }
+
     pub fn handle(&mut self, response: WingResponse) {
         match response {
             WingResponse::NodeData(node_id, data) => {
-                if let Some(requests) = self.node_data_requests.remove(&node_id) {
-                    let data = Arc::new(data);
-                    for req in requests {
-                        let _ = req.send(data.clone());
-                    }
-                }
+                self.resolve_node_data(node_id, Arc::new(data));
             }
             WingResponse::NodeDef(node_def) => {
+                let node_def = Arc::new(node_def);
+
                 if let Some(requests) = self.node_def_requests.remove(&node_def.id) {
-                    let node_def = Arc::new(node_def);
+                    self.pending_definitions = Some(PendingDefinitions::Node);
                     for req in requests {
                         let _ = req.send(node_def.clone());
                     }
+                } else if let Some(request) = self
+                    .node_children_requests
+                    .iter_mut()
+                    .find(|req| req.node_id == node_def.parent_id)
+                {
+                    self.pending_definitions =
+                        Some(PendingDefinitions::Children(node_def.parent_id));
+                    request.children.push(node_def);
+                } else {
+                    self.pending_definitions = Some(PendingDefinitions::Abandoned);
+                }
+            }
+            WingResponse::RequestEnd => {
+                let position = match self.pending_definitions.take() {
+                    Some(PendingDefinitions::Node | PendingDefinitions::Abandoned) => None,
+                    Some(PendingDefinitions::Children(node_id)) => self
+                        .node_children_requests
+                        .iter()
+                        .position(|req| req.node_id == node_id),
+                    // Nothing was received, so this ends a request for a node without
+                    // children. It can only be one that has not received any yet.
+                    None => self
+                        .node_children_requests
+                        .iter()
+                        .position(|req| req.children.is_empty()),
+                };
+
+                if let Some(request) =
+                    position.and_then(|idx| self.node_children_requests.remove(idx))
+                {
+                    let _ = request.sender.send(request.children);
                 }
             }
-            WingResponse::RequestEnd => {}
         }
     }
 }
 
 #[derive(Clone)]
 pub struct Wing {
-    console_thread_tx: Option<mpsc::Sender<WingRequest>>,
+    console_thread_tx: mpsc::Sender<WingRequest>,
+    request_policy: WingRequestPolicy,
 
-    console: Arc<Mutex<WingConsole>>,
-    requests: Arc<Mutex<WingRequests>>,
+    cache: Arc<Mutex<WingStateCache>>,
+    traffic_listener: Arc<RwLock<Option<WingTrafficListener>>>,
 }
 
 impl Wing {
-    pub fn channel<'a>(&'a self, channel: WingChannelId) -> WingChannel<'a> {
-        WingChannel::new(self, channel)
+    pub fn cache(&self) -> &Mutex<WingStateCache> {
+        &self.cache
+    }
+
+    fn cached(&self, node_id: i32) -> Option<CachedValue> {
+        self.cache.lock().unwrap().get(node_id)
     }
 
-    pub fn dca<'a>(&'a self, dca_id: WingDcaId) -> WingDca<'a> {
-        WingDca::new(self, dca_id)
+    /// Queues a write. The cache is updated by the writer thread once the value is sent.
+    /// Until then the node is not cached, so reads are queued behind the write.
+    fn write(&self, node_id: i32, value: WingValue) -> Result<(), WingError> {
+        self.cache.lock().unwrap().invalidate(node_id);
+        self.console_thread_tx
+            .send(WingRequest::Set(node_id, value))
+            .map_err(|_| WingError::ConsoleThreadStopped)
     }
 }
 
-impl Wing {
-    fn handle_incoming(&self) {
-        let Ok(response) = self.console.lock().unwrap().read() else {
-            return;
-        };
+/// State shared by the reader and the writer thread of a connection.
+#[derive(Clone)]
+struct WingIoState {
+    requests: Arc<Mutex<WingRequests>>,
+    cache: Arc<Mutex<WingStateCache>>,
+    traffic_listener: Arc<RwLock<Option<WingTrafficListener>>>,
+}
 
-        if let WingResponse::NodeData(id, data) = &response {
-            match WingConsole::id_to_defs(*id) {
-                None => println!("<Unknown:{}> = {}", id, data.get_string()),
-                Some(defs) if defs.is_empty() => {
-                    println!("<Unknown:{}> = {}", id, data.get_string())
-                }
-                Some(defs) if defs.len() == 1 => {
-                    println!("{} = {}", defs[0].0, data.get_string());
-                }
-                Some(defs) if (defs.len() > 1) => {
-                    let u = std::collections::HashSet::<u16>::from_iter(
-                        defs.iter().map(|(_, def)| def.index),
-                    );
-                    if u.len() == 1 {
-                        // let propname = String::from("/") + &defs[0].0.split("/").enumerate().filter(|(i, _)| *i < defs.len()-1).map(|(_, n)| n).collect::<Vec<_>>().join("/") +
-                        let propname = String::from("prop") + defs[0].1.index.to_string().as_str();
-                        println!("{} = {} (check out propmap.jsonl for more info on property with id {})", propname, data.get_string(), id);
-                    } else {
-                        println!("<MultiProp:{}> = {} (check out propmap.jsonl for more info on property id {})", id, data.get_string(), id);
-                    }
+impl WingIoState {
+    fn notify(&self, traffic: impl FnOnce() -> WingTraffic) {
+        if let Some(listener) = self.traffic_listener.read().unwrap().as_ref() {
+            listener(traffic());
+        }
+    }
+}
+
+/// The reader thread. It blocks in [`WingConsole::read`] and resolves requests with whatever
+/// the console sends. It stops when the connection breaks or is shut down by the writer, and
+/// fails all requests that are still pending.
+struct WingReader {
+    console: WingConsole,
+    state: WingIoState,
+}
+
+impl WingReader {
+    fn handle_incoming(&self, response: WingResponse) {
+        match response {
+            WingResponse::NodeData(node_id, data) => {
+                log::trace!("{} = {}", node_path(node_id), data.get_string());
+                self.state.notify(|| {
+                    WingTraffic::new(WingTrafficDirection::Received, node_id, data.get_string())
+                });
+
+                let data = Arc::new(data);
+                self.state
+                    .cache
+                    .lock()
+                    .unwrap()
+                    .update(node_id, CachedValue::Received(data.clone()));
+                self.state
+                    .requests
+                    .lock()
+                    .unwrap()
+                    .resolve_node_data(node_id, data);
+            }
+            response => self.state.requests.lock().unwrap().handle(response),
+        }
+    }
+
+    fn run(mut self) {
+        loop {
+            match self.console.read() {
+                Ok(response) => self.handle_incoming(response),
+                Err(err) => {
+                    log::info!("Stopped reading from Wing Console: {}", err);
+                    break;
                 }
-                Some(_) => {}
             }
         }
 
-        self.requests.lock().unwrap().handle(response);
+        // Dropping the senders fails the requests instead of letting them time out
+        *self.state.requests.lock().unwrap() = WingRequests::default();
     }
+}
 
-    pub fn make_console_thread_rx(&mut self) -> mpsc::Receiver<WingRequest> {
-        let (tx, rx) = mpsc::channel();
-        self.console_thread_tx = Some(tx);
-        rx
+/// The writer thread. It blocks on the request queue and sends writes and requests as soon
+/// as they arrive. It stops when it receives [`WingRequest::Shutdown`] or when every [`Wing`]
+/// handle has been dropped, and shuts the connection down, which also stops the reader.
+struct WingWriter {
+    console: WingConsole,
+    state: WingIoState,
+}
+
+impl WingWriter {
+    /// Sends a value and records it in the cache. A value that could not be sent stays
+    /// uncached, so that the next transaction does not skip it as unchanged.
+    fn write(&mut self, node_id: i32, value: WingValue) -> Result<(), libwing::Error> {
+        self.state
+            .notify(|| WingTraffic::new(WingTrafficDirection::Sent, node_id, value.to_string()));
+
+        let res = match &value {
+            WingValue::String(value) => self.console.set_string(node_id, value),
+            WingValue::Int(value) => self.console.set_int(node_id, *value),
+            WingValue::Float(value) => self.console.set_float(node_id, *value),
+        };
+
+        if res.is_ok() {
+            self.state
+                .cache
+                .lock()
+                .unwrap()
+                .update(node_id, CachedValue::Written(value));
+        }
+
+        res
     }
 
-    pub fn handle_incoming_loop(&self, rx: mpsc::Receiver<WingRequest>) {
-        loop {
-            for request in rx.try_iter() {
-                match request {
-                    WingRequest::NodeData(node_id, sender) => {
-                        self.requests
-                            .lock()
-                            .unwrap()
-                            .request_node_data(node_id, sender);
-                        self.console.lock().unwrap().request_node_data(node_id);
-                    }
-                    WingRequest::NodeDef(node_id, sender) => {
-                        self.requests
-                            .lock()
-                            .unwrap()
-                            .request_node_def(node_id, sender);
-                        self.console
-                            .lock()
-                            .unwrap()
-                            .request_node_definition(node_id);
-                    }
-                }
+    fn write_batch(&mut self, writes: Vec<(i32, WingValue)>) -> Result<(), libwing::Error> {
+        for (idx, chunk) in writes.chunks(WRITE_BATCH_CHUNK_SIZE).enumerate() {
+            if idx > 0 {
+                thread::sleep(WRITE_BATCH_CHUNK_PAUSE);
+            }
+
+            for (node_id, value) in chunk {
+                self.write(*node_id, value.clone())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Registers a request before it is sent, so the reader can resolve it.
+    fn register(&self, register: impl FnOnce(&mut WingRequests)) {
+        let mut requests = self.state.requests.lock().unwrap();
+        requests.prune();
+        register(&mut requests);
+    }
+
+    /// Executes a single request. Returns `false` if the thread should stop.
+    fn dispatch(&mut self, request: WingRequest) -> bool {
+        let res = match request {
+            WingRequest::NodeData(node_id, sender) => {
+                self.register(|requests| requests.request_node_data(node_id, sender));
+                self.console.request_node_data(node_id)
+            }
+            WingRequest::NodeDef(node_id, sender) => {
+                self.register(|requests| requests.request_node_def(node_id, sender));
+                self.console.request_node_definition(node_id)
             }
+            WingRequest::NodeChildren(node_id, sender) => {
+                self.register(|requests| requests.request_node_children(node_id, sender));
+                self.console.request_node_definition(node_id)
+            }
+            WingRequest::Set(node_id, value) => self.write(node_id, value),
+            WingRequest::SetBatch(writes) => self.write_batch(writes),
+            WingRequest::Shutdown => return false,
+        };
 
-            self.handle_incoming();
-            thread::sleep(Duration::from_secs_f32(1.0 / 100.0));
+        if let Err(err) = res {
+            log::error!("Failed to send to Wing Console: {}", err);
+        }
+
+        true
+    }
+
+    fn run(mut self, rx: mpsc::Receiver<WingRequest>) {
+        while let Ok(request) = rx.recv() {
+            if !self.dispatch(request) {
+                break;
+            }
+        }
+
+        if let Err(err) = self.console.shutdown() {
+            log::warn!("Failed to shut down Wing Console connection: {}", err);
         }
     }
 }
 
 impl Wing {
+    /// Sends a request to the writer thread and waits for its reply according to the
+    /// request policy. Returns `Ok(None)` if every attempt timed out.
+    async fn request_with_policy<T>(
+        &self,
+        make_request: impl Fn(oneshot::Sender<T>) -> WingRequest,
+    ) -> Result<Option<T>, WingError> {
+        for _ in 0..=self.request_policy.retries {
+            let (tx, rx) = oneshot::channel();
+            self.console_thread_tx
+                .send(make_request(tx))
+                .map_err(|_| WingError::ConsoleThreadStopped)?;
+
+            match tokio::time::timeout(self.request_policy.timeout, rx).await {
+                Ok(Ok(res)) => return Ok(Some(res)),
+                Ok(Err(_)) => return Err(WingError::ConsoleThreadStopped),
+                Err(_) => continue,
+            }
+        }
+
+        Ok(None)
+    }
+
     pub async fn request_data<F, R>(&self, node_id: i32, f: F) -> Result<R, WingError>
     where
         F: FnOnce(&WingNodeData) -> R,
     {
-        let console_tx = self
-            .console_thread_tx
-            .as_ref()
-            .ok_or(WingError::ConsoleTxNotReady)?;
-
-        let (tx, rx) = oneshot::channel();
-        let _ = console_tx.send(WingRequest::NodeData(node_id, tx));
+        let res = self
+            .request_with_policy(|tx| WingRequest::NodeData(node_id, tx))
+            .await?
+            .ok_or(WingError::NodeDataRequestTimeout(node_id))?;
+        Ok(f(&res))
+    }
 
-        let res = rx.await.expect("Sender was dropped");
+    pub async fn request_node_def<F, R>(&self, node_id: i32, f: F) -> Result<R, WingError>
+    where
+        F: FnOnce(&WingNodeDef) -> R,
+    {
+        let res = self
+            .request_with_policy(|tx| WingRequest::NodeDef(node_id, tx))
+            .await?
+            .ok_or(WingError::NodeDefRequestTimeout(node_id))?;
         Ok(f(&res))
     }
+}
 
-    pub async fn request_string(&self, node_id: i32) -> Result<String, WingError> {
-        self.request_data(node_id, |data| data.get_string()).await
+impl MixerBackend for Wing {
+    fn request_string(&self, node_id: i32) -> BackendFuture<'_, String> {
+        if let Some(value) = self.cached(node_id) {
+            return Box::pin(async move { Ok(value.as_string()) });
+        }
+        Box::pin(self.request_data(node_id, |data| data.get_string()))
     }
 
-    pub async fn request_int(&self, node_id: i32) -> Result<i32, WingError> {
-        self.request_data(node_id, |data| data.get_int()).await
+    fn request_int(&self, node_id: i32) -> BackendFuture<'_, i32> {
+        if let Some(value) = self.cached(node_id) {
+            return Box::pin(async move { Ok(value.as_int()) });
+        }
+        Box::pin(self.request_data(node_id, |data| data.get_int()))
     }
 
-    pub async fn request_float(&self, node_id: i32) -> Result<f32, WingError> {
-        self.request_data(node_id, |data| data.get_float()).await
+    fn request_float(&self, node_id: i32) -> BackendFuture<'_, f32> {
+        if let Some(value) = self.cached(node_id) {
+            return Box::pin(async move { Ok(value.as_float()) });
+        }
+        Box::pin(self.request_data(node_id, |data| data.get_float()))
     }
 
-    pub async fn request_node_def<F, R>(&self, node_id: i32, f: F) -> Result<R, WingError>
-    where
-        F: FnOnce(&WingNodeDef) -> R,
-    {
-        let console_tx = self
-            .console_thread_tx
-            .as_ref()
-            .ok_or(WingError::ConsoleTxNotReady)?;
+    fn set_string(&self, node_id: i32, value: &str) -> Result<(), WingError> {
+        self.write(node_id, WingValue::String(value.to_string()))
+    }
 
-        let (tx, rx) = oneshot::channel();
-        let _ = console_tx.send(WingRequest::NodeDef(node_id, tx));
+    fn set_int(&self, node_id: i32, value: i32) -> Result<(), WingError> {
+        self.write(node_id, WingValue::Int(value))
+    }
 
-        let res = rx.await.expect("Sender was dropped");
-        Ok(f(&res))
+    fn set_float(&self, node_id: i32, value: f32) -> Result<(), WingError> {
+        self.write(node_id, WingValue::Float(value))
     }
 
-    pub fn set_string(&self, node_id: i32, value: &str) -> Result<(), WingError> {
-        self.console.lock().unwrap().set_string(node_id, value)?;
-        Ok(())
+    fn request_node_children(&self, node_id: i32) -> BackendFuture<'_, Vec<Arc<WingNodeDef>>> {
+        Box::pin(async move {
+            self.request_with_policy(|tx| WingRequest::NodeChildren(node_id, tx))
+                .await?
+                .ok_or(WingError::NodeDefRequestTimeout(node_id))
+        })
     }
 
-    pub fn set_int(&self, node_id: i32, value: i32) -> Result<(), WingError> {
-        self.console.lock().unwrap().set_int(node_id, value)?;
-        Ok(())
+    fn cached_value(&self, node_id: i32) -> Option<CachedValue> {
+        self.cached(node_id)
     }
 
-    pub fn set_float(&self, node_id: i32, value: f32) -> Result<(), WingError> {
-        self.console.lock().unwrap().set_float(node_id, value)?;
-        Ok(())
+    fn write_batch(&self, writes: Vec<(i32, WingValue)>) -> Result<(), WingError> {
+        let mut cache = self.cache.lock().unwrap();
+        for (node_id, _) in &writes {
+            cache.invalidate(*node_id);
+        }
+        drop(cache);
+
+        self.console_thread_tx
+            .send(WingRequest::SetBatch(writes))
+            .map_err(|_| WingError::ConsoleThreadStopped)
+    }
+
+    fn set_traffic_listener(&self, listener: Option<WingTrafficListener>) {
+        *self.traffic_listener.write().unwrap() = listener;
     }
-}
 
-impl From<WingConsole> for Wing {
-    fn from(value: WingConsole) -> Self {
-        let mut wing = Wing {
-            console_thread_tx: None,
+    fn ping(&self) -> BackendFuture<'_, ()> {
+        let node_id = WingConsole::name_to_id(HEALTH_CHECK_NODE).unwrap();
+        Box::pin(self.request_data(node_id, |_| ()))
+    }
 
-            console: Arc::new(Mutex::new(value)),
+    fn close(&self) {
+        let _ = self.console_thread_tx.send(WingRequest::Shutdown);
+    }
+}
+
+impl Wing {
+    /// Starts the reader and the writer thread for a connected console. Requests are sent
+    /// according to `request_policy` for as long as the connection lasts.
+    pub fn new(console: WingConsole, request_policy: WingRequestPolicy) -> Result<Self, WingError> {
+        let (tx, rx) = mpsc::channel();
+        let state = WingIoState {
             requests: Arc::new(Mutex::new(WingRequests::default())),
+            cache: Arc::new(Mutex::new(WingStateCache::default())),
+            traffic_listener: Arc::new(RwLock::new(None)),
         };
 
-        let rx = wing.make_console_thread_rx();
-
-        tauri::async_runtime::spawn_blocking({
-            let wing = wing.clone();
-            move || wing.handle_incoming_loop(rx)
-        });
+        let reader = WingReader {
+            console: console.try_clone()?,
+            state: state.clone(),
+        };
+        let writer = WingWriter {
+            console,
+            state: state.clone(),
+        };
 
-        wing
+        thread::Builder::new()
+            .name("wing-reader".to_string())
+            .spawn(move || reader.run())
+            .expect("Failed to spawn console reader thread");
+        thread::Builder::new()
+            .name("wing-writer".to_string())
+            .spawn(move || writer.run(rx))
+            .expect("Failed to spawn console writer thread");
+
+        Ok(Wing {
+            console_thread_tx: tx,
+            request_policy,
+
+            cache: state.cache,
+            traffic_listener: state.traffic_listener,
+        })
     }
 }
@@ -1,6 +1,9 @@
 use libwing::WingConsole;
 
-use crate::wing::{error::WingError, id::WingId, Wing, WingColor};
+use crate::{
+    utils::Decibel,
+    wing::{db_to_fader, error::WingError, fader_to_db, id::WingId, MixerBackend, WingColor},
+};
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct WingDcaId(u8);
@@ -29,32 +32,77 @@ impl TryFrom<u8> for WingDcaId {
 }
 
 pub struct WingDca<'a> {
-    wing: &'a Wing,
+    wing: &'a dyn MixerBackend,
     id: WingDcaId,
 }
 
 impl<'a> WingDca<'a> {
-    pub fn new(wing: &'a Wing, id: WingDcaId) -> Self {
+    pub fn new(wing: &'a dyn MixerBackend, id: WingDcaId) -> Self {
         Self { wing, id }
     }
 }
 
 // DCAs
 impl<'a> WingDca<'a> {
-    fn get_dca_property(&self, property: &str) -> Option<i32> {
+    fn get_dca_property(&self, property: &str) -> Result<i32, WingError> {
         let name = format!("/dca/{}/{}", self.id.display(), property);
-        WingConsole::name_to_id(&name)
+        WingConsole::name_to_id(&name).ok_or(WingError::UnknownNodePath(name))
     }
 
     pub fn set_name(&self, new_name: &str) -> Result<(), WingError> {
         self.wing
-            .set_string(self.get_dca_property("name").unwrap(), new_name)?;
+            .set_string(self.get_dca_property("name")?, new_name)?;
         Ok(())
     }
 
+    pub fn mute(&self) -> Result<(), WingError> {
+        self.wing.set_int(self.get_dca_property("mute")?, 1)
+    }
+
+    pub fn unmute(&self) -> Result<(), WingError> {
+        self.wing.set_int(self.get_dca_property("mute")?, 0)
+    }
+
+    pub async fn get_fader_level(&self) -> Result<Decibel, WingError> {
+        let position = self
+            .wing
+            .request_float(self.get_dca_property("fdr")?)
+            .await?;
+        Ok(fader_to_db(position))
+    }
+
+    pub fn set_fader_level(&self, level: Decibel) -> Result<(), WingError> {
+        self.wing
+            .set_float(self.get_dca_property("fdr")?, db_to_fader(level))
+    }
+
     pub fn set_color(&self, color: WingColor) -> Result<(), WingError> {
         self.wing
-            .set_int(self.get_dca_property("col").unwrap(), color as i32)?;
+            .set_int(self.get_dca_property("col")?, color as i32)?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wing::{SimulatedWing, WingValue};
+
+    #[test]
+    fn fader_level_is_stored_in_db() {
+        let wing = SimulatedWing::default();
+        let backend: &dyn MixerBackend = &wing;
+        let dca = backend.dca(WingDcaId::new(3).unwrap());
+
+        dca.set_fader_level(Decibel::new(-12.5)).unwrap();
+        assert_eq!(wing.value_at("/dca/3/fdr"), Some(WingValue::Float(-12.5)));
+        let level = tauri::async_runtime::block_on(dca.get_fader_level()).unwrap();
+        assert_eq!(level, Decibel::new(-12.5));
+
+        dca.set_fader_level(Decibel::NEG_INFINITY).unwrap();
+        assert_eq!(
+            wing.value_at("/dca/3/fdr"),
+            Some(WingValue::Float(Decibel::MIN))
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use crate::wing::{error::WingError, params::bool_value, WingChannel, WingValue};
+
+/// Delay unit used for positions, the console also offers feet, metres and samples.
+const DELAY_MODE_MS: &str = "MS";
+
+// Delay
+impl<'a> WingChannel<'a> {
+    /// Channel delay in ms, `None` if the delay is off
+    pub async fn get_delay(&self) -> Result<Option<f32>, WingError> {
+        if !self.get_bool("dly/on").await? {
+            return Ok(None);
+        }
+        Ok(Some(self.get_float("dly/dly").await?))
+    }
+
+    /// Sets the channel delay in ms, `None` turns the delay off.
+    pub fn set_delay(&self, delay: Option<f32>) -> Result<(), WingError> {
+        let Some(delay) = delay else {
+            return self.set_value("dly/on", &bool_value(false));
+        };
+
+        self.set_value("dly/mode", &WingValue::String(DELAY_MODE_MS.to_string()))?;
+        self.set_value("dly/dly", &WingValue::Float(delay.max(0.0)))?;
+        self.set_value("dly/on", &bool_value(true))
+    }
+}
@@ -0,0 +1,168 @@
+use std::{
+    collections::HashSet,
+    net::{Ipv4Addr, SocketAddr, UdpSocket},
+    time::{Duration, Instant},
+};
+
+use crate::wing::error::WingError;
+
+pub const WING_DISCOVERY_PORT: u16 = 2222;
+
+const WING_DISCOVERY_PROBE: &[u8] = b"WING?";
+
+/// A console that answered the discovery probe.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct WingDiscoveryInfo {
+    pub ip: String,
+    pub name: String,
+    pub model: String,
+    pub serial: String,
+    pub firmware: String,
+}
+
+impl WingDiscoveryInfo {
+    /// Parses a discovery reply of the form `WING,<ip>,<name>,<model>,<serial>,<firmware>`.
+    pub fn parse(reply: &str) -> Option<Self> {
+        let mut parts = reply.trim_end_matches('\0').trim().split(',');
+
+        if parts.next()? != "WING" {
+            return None;
+        }
+
+        Some(Self {
+            ip: parts.next()?.to_string(),
+            name: parts.next()?.to_string(),
+            model: parts.next()?.to_string(),
+            serial: parts.next()?.to_string(),
+            firmware: parts.next()?.to_string(),
+        })
+    }
+}
+
+/// Broadcasts the discovery probe on the local network and collects the consoles that answer
+/// within `timeout`.
+pub fn discover_consoles(timeout: Duration) -> Result<Vec<WingDiscoveryInfo>, WingError> {
+    discover_consoles_at(
+        SocketAddr::from((Ipv4Addr::BROADCAST, WING_DISCOVERY_PORT)),
+        timeout,
+    )
+}
+
+/// Sends the discovery probe to `target` (which may be a broadcast address) and collects the
+/// consoles that answer within `timeout`.
+pub fn discover_consoles_at(
+    target: SocketAddr,
+    timeout: Duration,
+) -> Result<Vec<WingDiscoveryInfo>, WingError> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).map_err(WingError::DiscoveryError)?;
+    socket
+        .set_broadcast(true)
+        .map_err(WingError::DiscoveryError)?;
+    socket
+        .send_to(WING_DISCOVERY_PROBE, target)
+        .map_err(WingError::DiscoveryError)?;
+
+    let deadline = Instant::now() + timeout;
+    let mut seen = HashSet::new();
+    let mut consoles = Vec::new();
+    let mut buf = [0u8; 512];
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+
+        socket
+            .set_read_timeout(Some(remaining))
+            .map_err(WingError::DiscoveryError)?;
+
+        let len = match socket.recv_from(&mut buf) {
+            Ok((len, _)) => len,
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) =>
+            {
+                break
+            }
+            Err(err) => return Err(WingError::DiscoveryError(err)),
+        };
+
+        let Some(info) = WingDiscoveryInfo::parse(&String::from_utf8_lossy(&buf[..len])) else {
+            continue;
+        };
+
+        if seen.insert(info.ip.clone()) {
+            consoles.push(info);
+        }
+    }
+
+    Ok(consoles)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPLY: &str = "WING,192.168.1.20,FOH,wing-rack,S1234,3.0.5";
+
+    /// Answers a single probe with `replies`, like a console (or something else) on the network.
+    fn responder(replies: &'static [&'static str]) -> SocketAddr {
+        let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let addr = socket.local_addr().unwrap();
+
+        std::thread::spawn(move || {
+            let mut buf = [0u8; 64];
+            let (len, from) = socket.recv_from(&mut buf).unwrap();
+            assert_eq!(&buf[..len], WING_DISCOVERY_PROBE);
+
+            for reply in replies {
+                socket.send_to(reply.as_bytes(), from).unwrap();
+            }
+        });
+
+        addr
+    }
+
+    fn expected() -> WingDiscoveryInfo {
+        WingDiscoveryInfo {
+            ip: "192.168.1.20".to_string(),
+            name: "FOH".to_string(),
+            model: "wing-rack".to_string(),
+            serial: "S1234".to_string(),
+            firmware: "3.0.5".to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_reply() {
+        assert_eq!(WingDiscoveryInfo::parse(REPLY), Some(expected()));
+        assert_eq!(
+            WingDiscoveryInfo::parse(&format!("{}\0\0", REPLY)),
+            Some(expected())
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_replies() {
+        assert_eq!(WingDiscoveryInfo::parse(""), None);
+        assert_eq!(WingDiscoveryInfo::parse("X32,192.168.1.20,FOH"), None);
+        assert_eq!(WingDiscoveryInfo::parse("WING,192.168.1.20,FOH"), None);
+    }
+
+    #[test]
+    fn discovers_local_responder() {
+        let addr = responder(&[REPLY]);
+        let consoles = discover_consoles_at(addr, Duration::from_millis(300)).unwrap();
+        assert_eq!(consoles, vec![expected()]);
+    }
+
+    #[test]
+    fn ignores_malformed_and_duplicate_replies() {
+        let addr = responder(&["HELLO", "WING,192.168.1.20", REPLY, REPLY]);
+        let consoles = discover_consoles_at(addr, Duration::from_millis(300)).unwrap();
+        assert_eq!(consoles, vec![expected()]);
+    }
+}
@@ -0,0 +1,187 @@
+use crate::wing::{
+    error::WingError, params::bool_value, WingChannel, WingParameterBlock, WingValue,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WingGate {
+    pub on: bool,
+    /// Threshold in dB
+    pub threshold: f32,
+    /// Attenuation of the closed gate in dB
+    pub range: f32,
+    /// Attack in ms
+    pub attack: f32,
+    /// Hold in ms
+    pub hold: f32,
+    /// Release in ms
+    pub release: f32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WingCompressor {
+    pub on: bool,
+    /// Threshold in dB
+    pub threshold: f32,
+    /// Ratio as x:1
+    pub ratio: f32,
+    /// Knee width in dB
+    pub knee: f32,
+    /// Attack in ms
+    pub attack: f32,
+    /// Hold in ms
+    pub hold: f32,
+    /// Release in ms
+    pub release: f32,
+    /// Makeup gain in dB
+    pub makeup: f32,
+}
+
+/// Gate and compressor of a strip.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WingDynamics {
+    pub gate: WingGate,
+    pub compressor: WingCompressor,
+}
+
+impl WingParameterBlock for WingGate {
+    fn parameters(&self) -> Vec<(String, WingValue)> {
+        vec![
+            ("gate/on".to_string(), bool_value(self.on)),
+            ("gate/thr".to_string(), WingValue::Float(self.threshold)),
+            ("gate/range".to_string(), WingValue::Float(self.range)),
+            ("gate/att".to_string(), WingValue::Float(self.attack)),
+            ("gate/hld".to_string(), WingValue::Float(self.hold)),
+            ("gate/rel".to_string(), WingValue::Float(self.release)),
+        ]
+    }
+}
+
+impl WingParameterBlock for WingCompressor {
+    fn parameters(&self) -> Vec<(String, WingValue)> {
+        vec![
+            ("dyn/on".to_string(), bool_value(self.on)),
+            ("dyn/thr".to_string(), WingValue::Float(self.threshold)),
+            ("dyn/ratio".to_string(), WingValue::Float(self.ratio)),
+            ("dyn/knee".to_string(), WingValue::Float(self.knee)),
+            ("dyn/att".to_string(), WingValue::Float(self.attack)),
+            ("dyn/hld".to_string(), WingValue::Float(self.hold)),
+            ("dyn/rel".to_string(), WingValue::Float(self.release)),
+            ("dyn/gain".to_string(), WingValue::Float(self.makeup)),
+        ]
+    }
+}
+
+impl WingParameterBlock for WingDynamics {
+    fn parameters(&self) -> Vec<(String, WingValue)> {
+        let mut parameters = self.gate.parameters();
+        parameters.extend(self.compressor.parameters());
+        parameters
+    }
+}
+
+// Dynamics
+impl<'a> WingChannel<'a> {
+    pub async fn get_gate(&self) -> Result<WingGate, WingError> {
+        Ok(WingGate {
+            on: self.get_bool("gate/on").await?,
+            threshold: self.get_float("gate/thr").await?,
+            range: self.get_float("gate/range").await?,
+            attack: self.get_float("gate/att").await?,
+            hold: self.get_float("gate/hld").await?,
+            release: self.get_float("gate/rel").await?,
+        })
+    }
+
+    pub fn set_gate(&self, gate: &WingGate) -> Result<(), WingError> {
+        gate.write(self)
+    }
+
+    pub async fn get_compressor(&self) -> Result<WingCompressor, WingError> {
+        Ok(WingCompressor {
+            on: self.get_bool("dyn/on").await?,
+            threshold: self.get_float("dyn/thr").await?,
+            ratio: self.get_float("dyn/ratio").await?,
+            knee: self.get_float("dyn/knee").await?,
+            attack: self.get_float("dyn/att").await?,
+            hold: self.get_float("dyn/hld").await?,
+            release: self.get_float("dyn/rel").await?,
+            makeup: self.get_float("dyn/gain").await?,
+        })
+    }
+
+    pub fn set_compressor(&self, compressor: &WingCompressor) -> Result<(), WingError> {
+        compressor.write(self)
+    }
+
+    pub async fn get_dynamics(&self) -> Result<WingDynamics, WingError> {
+        Ok(WingDynamics {
+            gate: self.get_gate().await?,
+            compressor: self.get_compressor().await?,
+        })
+    }
+
+    pub fn set_dynamics(&self, dynamics: &WingDynamics) -> Result<(), WingError> {
+        dynamics.write(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wing::{
+        params::tests::assert_parameters_fit, MixerBackend, SimulatedWing, WingStripId,
+        WingStripKind,
+    };
+
+    fn dynamics() -> WingDynamics {
+        WingDynamics {
+            gate: WingGate {
+                on: true,
+                threshold: -40.0,
+                range: 60.0,
+                attack: 0.5,
+                hold: 20.0,
+                release: 200.0,
+            },
+            compressor: WingCompressor {
+                on: false,
+                threshold: -20.0,
+                ratio: 3.0,
+                knee: 2.0,
+                attack: 10.0,
+                hold: 5.0,
+                release: 100.0,
+                makeup: 4.0,
+            },
+        }
+    }
+
+    #[test]
+    fn parameters_fit_every_input_strip() {
+        let wing = SimulatedWing::default();
+        let backend: &dyn MixerBackend = &wing;
+
+        for kind in [WingStripKind::Channel, WingStripKind::Aux] {
+            for number in kind.id_range() {
+                let channel = backend.channel(WingStripId::new(kind, number).unwrap());
+                assert_parameters_fit(&channel, &dynamics());
+            }
+        }
+    }
+
+    #[test]
+    fn dynamics_are_read_back_as_written() {
+        let wing = SimulatedWing::default();
+        let backend: &dyn MixerBackend = &wing;
+        let channel = backend.channel(WingStripId::new(WingStripKind::Channel, 1).unwrap());
+
+        channel.set_dynamics(&dynamics()).unwrap();
+        assert_eq!(
+            tauri::async_runtime::block_on(channel.get_dynamics()).unwrap(),
+            dynamics()
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use crate::wing::{
+    error::WingError, params::bool_value, WingChannel, WingParameterBlock, WingValue,
+};
+
+/// Number of fully parametric bands between the low and the high band.
+pub const WING_EQ_BANDS: usize = 4;
+
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WingLowCut {
+    pub on: bool,
+    /// Cutoff frequency in Hz
+    pub frequency: f32,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WingEqBand {
+    /// Gain in dB
+    pub gain: f32,
+    /// Center (or corner) frequency in Hz
+    pub frequency: f32,
+    pub q: f32,
+}
+
+/// The low and the high band can either be a shelf or another parametric band.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum WingEqBandType {
+    Parametric,
+    Shelf,
+}
+
+impl WingEqBandType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Parametric => "PEQ",
+            Self::Shelf => "SHV",
+        }
+    }
+
+    fn from_value(value: &str) -> Result<Self, WingError> {
+        match value {
+            "PEQ" => Ok(Self::Parametric),
+            "SHV" => Ok(Self::Shelf),
+            _ => Err(WingError::InvalidEqBandType(value.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WingEqShelf {
+    pub band_type: WingEqBandType,
+    #[serde(flatten)]
+    pub band: WingEqBand,
+}
+
+/// The complete EQ block of a strip, including the low cut filter.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WingEq {
+    pub on: bool,
+    pub low_cut: WingLowCut,
+    pub low: WingEqShelf,
+    pub bands: [WingEqBand; WING_EQ_BANDS],
+    pub high: WingEqShelf,
+}
+
+impl WingParameterBlock for WingEq {
+    fn parameters(&self) -> Vec<(String, WingValue)> {
+        let mut parameters = vec![
+            ("flt/lc".to_string(), bool_value(self.low_cut.on)),
+            (
+                "flt/lcf".to_string(),
+                WingValue::Float(self.low_cut.frequency),
+            ),
+            ("eq/on".to_string(), bool_value(self.on)),
+        ];
+
+        let shelf_parameters = |prefix: &str, shelf: &WingEqShelf| {
+            [
+                (
+                    format!("eq/{}eq", prefix),
+                    WingValue::String(shelf.band_type.as_str().to_string()),
+                ),
+                (format!("eq/{}g", prefix), WingValue::Float(shelf.band.gain)),
+                (
+                    format!("eq/{}f", prefix),
+                    WingValue::Float(shelf.band.frequency),
+                ),
+                (format!("eq/{}q", prefix), WingValue::Float(shelf.band.q)),
+            ]
+        };
+
+        parameters.extend(shelf_parameters("l", &self.low));
+        for (idx, band) in self.bands.iter().enumerate() {
+            let band_number = idx + 1;
+            parameters.extend([
+                (format!("eq/{}g", band_number), WingValue::Float(band.gain)),
+                (
+                    format!("eq/{}f", band_number),
+                    WingValue::Float(band.frequency),
+                ),
+                (format!("eq/{}q", band_number), WingValue::Float(band.q)),
+            ]);
+        }
+        parameters.extend(shelf_parameters("h", &self.high));
+
+        parameters
+    }
+}
+
+// EQ
+impl<'a> WingChannel<'a> {
+    async fn get_eq_band(&self, prefix: &str) -> Result<WingEqBand, WingError> {
+        Ok(WingEqBand {
+            gain: self.get_float(&format!("eq/{}g", prefix)).await?,
+            frequency: self.get_float(&format!("eq/{}f", prefix)).await?,
+            q: self.get_float(&format!("eq/{}q", prefix)).await?,
+        })
+    }
+
+    async fn get_eq_shelf(&self, prefix: &str) -> Result<WingEqShelf, WingError> {
+        let band_type = self.get_string(&format!("eq/{}eq", prefix)).await?;
+
+        Ok(WingEqShelf {
+            band_type: WingEqBandType::from_value(&band_type)?,
+            band: self.get_eq_band(prefix).await?,
+        })
+    }
+
+    pub async fn get_eq(&self) -> Result<WingEq, WingError> {
+        let low_cut = WingLowCut {
+            on: self.get_bool("flt/lc").await?,
+            frequency: self.get_float("flt/lcf").await?,
+        };
+
+        let mut bands = Vec::with_capacity(WING_EQ_BANDS);
+        for band_number in 1..=WING_EQ_BANDS {
+            bands.push(self.get_eq_band(&band_number.to_string()).await?);
+        }
+
+        Ok(WingEq {
+            on: self.get_bool("eq/on").await?,
+            low_cut,
+            low: self.get_eq_shelf("l").await?,
+            bands: bands.try_into().unwrap(),
+            high: self.get_eq_shelf("h").await?,
+        })
+    }
+
+    pub fn set_eq(&self, eq: &WingEq) -> Result<(), WingError> {
+        eq.write(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wing::{
+        params::tests::assert_parameters_fit, MixerBackend, SimulatedWing, WingStripId,
+        WingStripKind,
+    };
+
+    fn eq() -> WingEq {
+        let band = WingEqBand {
+            gain: 0.0,
+            frequency: 1000.0,
+            q: 2.0,
+        };
+        WingEq {
+            on: true,
+            low_cut: WingLowCut {
+                on: false,
+                frequency: 80.0,
+            },
+            low: WingEqShelf {
+                band_type: WingEqBandType::Shelf,
+                band,
+            },
+            bands: [band; WING_EQ_BANDS],
+            high: WingEqShelf {
+                band_type: WingEqBandType::Parametric,
+                band,
+            },
+        }
+    }
+
+    #[test]
+    fn parameters_fit_every_input_strip() {
+        let wing = SimulatedWing::default();
+        let backend: &dyn MixerBackend = &wing;
+
+        for kind in [WingStripKind::Channel, WingStripKind::Aux] {
+            for number in kind.id_range() {
+                let channel = backend.channel(WingStripId::new(kind, number).unwrap());
+                assert_parameters_fit(&channel, &eq());
+            }
+        }
+    }
+
+    #[test]
+    fn eq_is_read_back_as_written() {
+        let wing = SimulatedWing::default();
+        let backend: &dyn MixerBackend = &wing;
+        let channel = backend.channel(WingStripId::new(WingStripKind::Channel, 1).unwrap());
+
+        channel.set_eq(&eq()).unwrap();
+        assert_eq!(
+            tauri::async_runtime::block_on(channel.get_eq()).unwrap(),
+            eq()
+        );
+    }
+
+    #[test]
+    fn unknown_band_types_are_rejected() {
+        for band_type in [WingEqBandType::Parametric, WingEqBandType::Shelf] {
+            assert_eq!(
+                WingEqBandType::from_value(band_type.as_str()).unwrap(),
+                band_type
+            );
+        }
+        assert!(WingEqBandType::from_value("").is_err());
+        assert!(WingEqBandType::from_value("HPF").is_err());
+    }
+}
@@ -5,7 +5,14 @@ pub enum WingError {
     IdOutOfBounds(u32, RangeInclusive<u32>),
     LibWingError(libwing::Error),
     NodeDataRequestTimeout(i32),
-    ConsoleTxNotReady,
+    NodeDefRequestTimeout(i32),
+    ConsoleThreadStopped,
+    DiscoveryError(std::io::Error),
+    UnknownNodePath(String),
+    NoInputSource(String),
+    MeterError(std::io::Error),
+    InvalidColor(i32),
+    InvalidEqBandType(String),
 }
 
 impl WingError {
@@ -29,8 +36,21 @@ impl std::fmt::Display for WingError {
             Self::NodeDataRequestTimeout(node_id) => {
                 write!(f, "Node data request timed out for node ID {}", node_id)
             }
+            Self::NodeDefRequestTimeout(node_id) => {
+                write!(
+                    f,
+                    "Node definition request timed out for node ID {}",
+                    node_id
+                )
+            }
             Self::LibWingError(error) => write!(f, "libwing error: {}", error),
-            Self::ConsoleTxNotReady => write!(f, "Console tx not ready"),
+            Self::ConsoleThreadStopped => write!(f, "Console thread has stopped"),
+            Self::DiscoveryError(error) => write!(f, "Console discovery failed: {}", error),
+            Self::UnknownNodePath(path) => write!(f, "Unknown node path: {}", path),
+            Self::NoInputSource(strip) => write!(f, "{} is not connected to an input", strip),
+            Self::MeterError(error) => write!(f, "Metering failed: {}", error),
+            Self::InvalidColor(value) => write!(f, "{} is not a valid colour", value),
+            Self::InvalidEqBandType(value) => write!(f, "{} is not a valid EQ band type", value),
         }
     }
 }
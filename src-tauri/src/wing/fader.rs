@@ -0,0 +1,31 @@
+use crate::utils::Decibel;
+
+/// Highest level a fader or send can be set to.
+pub const FADER_MAX_DB: f32 = 10.0;
+
+/// Converts the value of a fader or send level node to a level.
+///
+/// The Wing stores these levels in dB, from [`Decibel::MIN`] (-inf) to [`FADER_MAX_DB`].
+pub fn fader_to_db(value: f32) -> Decibel {
+    Decibel::new(value).clamp(Decibel::MIN, FADER_MAX_DB)
+}
+
+/// Converts a level to the value of a fader or send level node, the inverse of
+/// [`fader_to_db`]. Levels above [`FADER_MAX_DB`] are clamped, -inf is [`Decibel::MIN`].
+pub fn db_to_fader(level: Decibel) -> f32 {
+    level.clamp(Decibel::MIN, FADER_MAX_DB).as_f32()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_are_written_in_db() {
+        assert_eq!(db_to_fader(Decibel::new(-10.0)), -10.0);
+        assert_eq!(db_to_fader(Decibel::NEG_INFINITY), Decibel::MIN);
+        assert_eq!(db_to_fader(Decibel::new(20.0)), FADER_MAX_DB);
+        assert_eq!(fader_to_db(-30.0), Decibel::new(-30.0));
+        assert!(fader_to_db(-200.0).is_neg_infinity());
+    }
+}
@@ -0,0 +1,120 @@
+use libwing::WingConsole;
+
+use crate::wing::{
+    error::WingError,
+    id::{deserialize_id, WingId},
+    MixerBackend,
+};
+
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, specta::Type,
+)]
+pub struct WingFxSlotId(u8);
+
+impl WingId for WingFxSlotId {
+    type Id = u8;
+
+    const MIN_ID: u8 = 1;
+    const MAX_ID: u8 = 16;
+
+    fn unchecked_new(id: u8) -> Self {
+        Self(id)
+    }
+
+    fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for WingFxSlotId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_id(deserializer)
+    }
+}
+
+impl TryFrom<u8> for WingFxSlotId {
+    type Error = WingError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+/// FX parameters that are commonly changed during a show. Which of them exist depends on the
+/// model loaded into the slot.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    specta::Type,
+)]
+pub enum WingFxParameter {
+    /// Reverb decay in s
+    Decay,
+    /// Reverb pre-delay in ms
+    PreDelay,
+    /// Delay time in ms
+    DelayTime,
+}
+
+impl WingFxParameter {
+    fn property(&self) -> &'static str {
+        match self {
+            Self::Decay => "decay",
+            Self::PreDelay => "pdel",
+            Self::DelayTime => "time",
+        }
+    }
+}
+
+pub struct WingFxSlot<'a> {
+    wing: &'a dyn MixerBackend,
+    id: WingFxSlotId,
+}
+
+impl<'a> WingFxSlot<'a> {
+    pub fn new(wing: &'a dyn MixerBackend, id: WingFxSlotId) -> Self {
+        Self { wing, id }
+    }
+}
+
+impl<'a> WingFxSlot<'a> {
+    fn get_fx_property(&self, property: &str) -> Result<i32, WingError> {
+        let name = format!("/fx/{}/{}", self.id.display(), property);
+        WingConsole::name_to_id(&name).ok_or(WingError::UnknownNodePath(name))
+    }
+
+    /// Name of the model loaded into the slot, e.g. `HALL`
+    pub async fn get_model(&self) -> Result<String, WingError> {
+        self.wing.request_string(self.get_fx_property("mdl")?).await
+    }
+
+    pub async fn is_engaged(&self) -> Result<bool, WingError> {
+        let int_data = self.wing.request_int(self.get_fx_property("on")?).await?;
+        Ok(int_data != 0)
+    }
+
+    /// Engages (`true`) or bypasses (`false`) the slot.
+    pub fn set_engaged(&self, engaged: bool) -> Result<(), WingError> {
+        self.wing
+            .set_int(self.get_fx_property("on")?, engaged as i32)
+    }
+
+    pub async fn get_parameter(&self, parameter: WingFxParameter) -> Result<f32, WingError> {
+        self.wing
+            .request_float(self.get_fx_property(parameter.property())?)
+            .await
+    }
+
+    pub fn set_parameter(&self, parameter: WingFxParameter, value: f32) -> Result<(), WingError> {
+        self.wing
+            .set_float(self.get_fx_property(parameter.property())?, value)
+    }
+}
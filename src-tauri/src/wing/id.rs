@@ -39,3 +39,30 @@ pub trait WingId: Sized {
         Helper(self)
     }
 }
+
+/// Deserializes an id, rejecting values outside of `MIN_ID..=MAX_ID` like [`WingId::new`].
+pub fn deserialize_id<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    T: WingId,
+    T::Id: serde::Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    let value = <T::Id as serde::Deserialize>::deserialize(deserializer)?;
+    T::new(value).map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::wing::{WingBusId, WingFxSlotId, WingMatrixId, WingMuteGroupId};
+
+    #[test]
+    fn deserializing_checks_the_range() {
+        assert!(serde_json::from_str::<WingMuteGroupId>("8").is_ok());
+        assert!(serde_json::from_str::<WingMuteGroupId>("0").is_err());
+        assert!(serde_json::from_str::<WingMuteGroupId>("200").is_err());
+        assert!(serde_json::from_str::<WingFxSlotId>("17").is_err());
+        assert!(serde_json::from_str::<WingBusId>("16").is_ok());
+        assert!(serde_json::from_str::<WingBusId>("0").is_err());
+        assert!(serde_json::from_str::<WingMatrixId>("9").is_err());
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    utils::Decibel,
+    wing::{error::WingError, node_id_from_path, WingChannel, WingValue},
+};
+
+/// Range of the digital trim of a strip in dB.
+pub const TRIM_MIN_DB: f32 = -18.0;
+pub const TRIM_MAX_DB: f32 = 18.0;
+
+/// Source group of a channel that is not connected to any input.
+const UNCONNECTED_SOURCE_GROUP: &str = "OFF";
+
+/// A physical (or virtual) input a channel is fed from, e.g. local input 3 or AES50 A 12.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WingInputSource {
+    /// Input group as named by the console (`LCL`, `A`, `B`, ...)
+    pub group: String,
+    pub input: u8,
+}
+
+impl WingInputSource {
+    /// Path of a property of the input node, e.g. `/io/in/LCL/3/name`
+    pub(super) fn node_path(&self, property: &str) -> String {
+        format!("/io/in/{}/{}/{}", self.group, self.input, property)
+    }
+}
+
+// Input
+impl<'a> WingChannel<'a> {
+    /// Digital trim of the strip in dB, applied after the source's input gain.
+    pub async fn get_trim(&self) -> Result<Decibel, WingError> {
+        Ok(self.get_float("in/set/trim").await?.into())
+    }
+
+    /// Trims outside of [`TRIM_MIN_DB`]..=[`TRIM_MAX_DB`] are clamped.
+    pub fn set_trim(&self, trim: Decibel) -> Result<(), WingError> {
+        let trim = trim.as_f32().clamp(TRIM_MIN_DB, TRIM_MAX_DB);
+        self.set_value("in/set/trim", &WingValue::Float(trim))
+    }
+
+    /// The input the strip is fed from, `None` if it is not connected.
+    pub async fn get_input_source(&self) -> Result<Option<WingInputSource>, WingError> {
+        let group = self.get_string("in/conn/grp").await?;
+        if group.is_empty() || group == UNCONNECTED_SOURCE_GROUP {
+            return Ok(None);
+        }
+
+        let input = self.get_float("in/conn/in").await? as u8;
+        Ok(Some(WingInputSource { group, input }))
+    }
+
+    async fn input_gain_node(&self) -> Result<i32, WingError> {
+        let Some(source) = self.get_input_source().await? else {
+            return Err(WingError::NoInputSource(self.id().to_string()));
+        };
+
+        node_id_from_path(&source.node_path("g"))
+    }
+
+    /// Gain of the input the strip is fed from. This is shared by all strips using the same
+    /// input.
+    pub async fn get_input_gain(&self) -> Result<Decibel, WingError> {
+        let node_id = self.input_gain_node().await?;
+        Ok(self.backend().request_float(node_id).await?.into())
+    }
+
+    pub async fn set_input_gain(&self, gain: Decibel) -> Result<(), WingError> {
+        let node_id = self.input_gain_node().await?;
+        self.backend().set_float(node_id, gain.as_f32())
+    }
+}
@@ -0,0 +1,389 @@
+use std::{
+    io::Write,
+    net::{Ipv4Addr, TcpStream, ToSocketAddrs, UdpSocket},
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    utils::Decibel,
+    wing::{error::WingError, id::WingId, WingChannelId, WingDcaId},
+};
+
+/// TCP port of the console's native protocol, which also takes the meter requests.
+const WING_NATIVE_PORT: u16 = 2222;
+
+/// Meter requests expire on the console after a few seconds and have to be renewed.
+const METER_RENEW_INTERVAL: Duration = Duration::from_secs(4);
+const METER_READ_TIMEOUT: Duration = Duration::from_millis(100);
+const METER_CONNECT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Identifies our meter reports among others sent to the same port.
+const METER_REPORT_ID: u32 = 0x6d78_0001;
+
+// Native protocol tokens used for meter requests
+const NATIVE_CHANNEL_SELECT: u8 = 0xdf;
+const NATIVE_METER_CHANNEL: u8 = 0xd3;
+const METER_SET_PORT: u8 = 0xd3;
+const METER_SET_REPORT_ID: u8 = 0xd4;
+const METER_COLLECTION_START: u8 = 0xdc;
+const METER_COLLECTION_END: u8 = 0xde;
+const METER_CHANNEL: u8 = 0xa0;
+const METER_DCA: u8 = 0xa6;
+
+/// Values reported per channel (input L/R, output L/R, gate and dynamics key and gain).
+const CHANNEL_METER_VALUES: usize = 8;
+/// Index of the left output value of a channel, the right one follows.
+const CHANNEL_METER_OUTPUT: usize = 2;
+/// Values reported per DCA (pre and post fader level).
+const DCA_METER_VALUES: usize = 2;
+/// Index of the post fader value of a DCA.
+const DCA_METER_POST: usize = 1;
+
+/// Latest meter levels, written by a [`MeterSource`] and read by whoever displays them.
+///
+/// Every level is a single atomic, so writers never wait for readers (and vice versa).
+pub struct WingMeterStore {
+    channels: [AtomicU32; WingChannelId::MAX_ID as usize],
+    dcas: [AtomicU32; WingDcaId::MAX_ID as usize],
+}
+
+impl Default for WingMeterStore {
+    fn default() -> Self {
+        let silence = || AtomicU32::new(Decibel::NEG_INFINITY.as_f32().to_bits());
+        Self {
+            channels: std::array::from_fn(|_| silence()),
+            dcas: std::array::from_fn(|_| silence()),
+        }
+    }
+}
+
+impl WingMeterStore {
+    fn load(level: &AtomicU32) -> Decibel {
+        f32::from_bits(level.load(Ordering::Relaxed)).into()
+    }
+
+    fn store(level: &AtomicU32, value: Decibel) {
+        level.store(value.as_f32().to_bits(), Ordering::Relaxed);
+    }
+
+    pub fn channel(&self, channel_id: WingChannelId) -> Decibel {
+        Self::load(&self.channels[channel_id.value() as usize - 1])
+    }
+
+    pub fn set_channel(&self, channel_id: WingChannelId, level: Decibel) {
+        Self::store(&self.channels[channel_id.value() as usize - 1], level);
+    }
+
+    pub fn dca(&self, dca_id: WingDcaId) -> Decibel {
+        Self::load(&self.dcas[dca_id.value() as usize - 1])
+    }
+
+    pub fn set_dca(&self, dca_id: WingDcaId, level: Decibel) {
+        Self::store(&self.dcas[dca_id.value() as usize - 1], level);
+    }
+
+    /// Sets every level to -inf.
+    pub fn clear(&self) {
+        for level in self.channels.iter().chain(self.dcas.iter()) {
+            Self::store(level, Decibel::NEG_INFINITY);
+        }
+    }
+}
+
+/// Something that produces meter levels.
+pub trait MeterSource: Send + 'static {
+    /// Prepares the source, e.g. connects to the console. Called before [`Self::run`], so that
+    /// failures can be reported to whoever started metering.
+    fn open(&mut self) -> Result<(), WingError> {
+        Ok(())
+    }
+
+    /// Feeds `store` until `stop` is set. Runs on its own thread.
+    fn run(self: Box<Self>, store: &WingMeterStore, stop: &AtomicBool) -> Result<(), WingError>;
+}
+
+/// Meters of a real console. The console sends them via UDP after they have been requested
+/// through the native protocol.
+pub struct WingMeterSource {
+    address: String,
+    connection: Option<(UdpSocket, TcpStream)>,
+}
+
+impl WingMeterSource {
+    pub fn new(address: String) -> Self {
+        Self {
+            address,
+            connection: None,
+        }
+    }
+
+    fn request(&self, stream: &mut TcpStream, port: u16) -> Result<(), WingError> {
+        let mut request = vec![NATIVE_CHANNEL_SELECT, NATIVE_METER_CHANNEL, METER_SET_PORT];
+        request.extend(port.to_be_bytes());
+        request.push(METER_SET_REPORT_ID);
+        request.extend(METER_REPORT_ID.to_be_bytes());
+
+        request.push(METER_COLLECTION_START);
+        for channel_idx in 0..WingChannelId::MAX_ID {
+            request.extend([METER_CHANNEL, channel_idx]);
+        }
+        for dca_idx in 0..WingDcaId::MAX_ID {
+            request.extend([METER_DCA, dca_idx]);
+        }
+        request.push(METER_COLLECTION_END);
+
+        stream.write_all(&request).map_err(WingError::MeterError)
+    }
+
+    /// Parses a meter report. Levels are signed 16 bit values in 1/256 dB.
+    fn parse(report: &[u8], store: &WingMeterStore) {
+        let Some((id, values)) = report.split_first_chunk::<4>() else {
+            return;
+        };
+        if u32::from_be_bytes(*id) != METER_REPORT_ID {
+            return;
+        }
+
+        let values = values
+            .chunks_exact(2)
+            .map(|value| Decibel::new(i16::from_be_bytes([value[0], value[1]]) as f32 / 256.0))
+            .collect::<Vec<_>>();
+
+        let (channels, dcas) = values
+            .split_at((WingChannelId::MAX_ID as usize * CHANNEL_METER_VALUES).min(values.len()));
+
+        for (idx, channel) in channels.chunks_exact(CHANNEL_METER_VALUES).enumerate() {
+            let Ok(channel_id) = WingChannelId::new(idx as u8 + 1) else {
+                continue;
+            };
+            let output = &channel[CHANNEL_METER_OUTPUT..CHANNEL_METER_OUTPUT + 2];
+            let level = Decibel::new(output[0].as_f32().max(output[1].as_f32()));
+            store.set_channel(channel_id, level);
+        }
+
+        for (idx, dca) in dcas.chunks_exact(DCA_METER_VALUES).enumerate() {
+            let Ok(dca_id) = WingDcaId::new(idx as u8 + 1) else {
+                continue;
+            };
+            store.set_dca(dca_id, dca[DCA_METER_POST]);
+        }
+    }
+}
+
+impl MeterSource for WingMeterSource {
+    fn open(&mut self) -> Result<(), WingError> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).map_err(WingError::MeterError)?;
+        socket
+            .set_read_timeout(Some(METER_READ_TIMEOUT))
+            .map_err(WingError::MeterError)?;
+
+        let address = (self.address.as_str(), WING_NATIVE_PORT)
+            .to_socket_addrs()
+            .map_err(WingError::MeterError)?
+            .next()
+            .ok_or_else(|| {
+                WingError::MeterError(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    format!("Unknown address: {}", self.address),
+                ))
+            })?;
+        let stream = TcpStream::connect_timeout(&address, METER_CONNECT_TIMEOUT)
+            .map_err(WingError::MeterError)?;
+
+        self.connection = Some((socket, stream));
+        Ok(())
+    }
+
+    fn run(
+        mut self: Box<Self>,
+        store: &WingMeterStore,
+        stop: &AtomicBool,
+    ) -> Result<(), WingError> {
+        if self.connection.is_none() {
+            self.open()?;
+        }
+        let (socket, mut stream) = self.connection.take().unwrap();
+        let port = socket.local_addr().map_err(WingError::MeterError)?.port();
+
+        let mut last_request: Option<Instant> = None;
+        let mut buf = [0u8; 2048];
+        while !stop.load(Ordering::Relaxed) {
+            if last_request.is_none_or(|at| at.elapsed() >= METER_RENEW_INTERVAL) {
+                self.request(&mut stream, port)?;
+                last_request = Some(Instant::now());
+            }
+
+            match socket.recv(&mut buf) {
+                Ok(len) => Self::parse(&buf[..len], store),
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                    ) => {}
+                Err(err) => return Err(WingError::MeterError(err)),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Made up meter levels, so that metering can be used without a console.
+pub struct SimulatedMeterSource {
+    interval: Duration,
+}
+
+impl SimulatedMeterSource {
+    pub fn new(interval: Duration) -> Self {
+        Self { interval }
+    }
+
+    /// Every channel "talks" in bursts of a few seconds, offset against the others.
+    fn channel_level(channel_idx: u8, t: f32) -> Decibel {
+        let phase = t * (0.3 + channel_idx as f32 * 0.07) + channel_idx as f32;
+        if phase.sin() < 0.0 {
+            return Decibel::NEG_INFINITY;
+        }
+
+        let syllables = (t * 6.0 + channel_idx as f32).sin().abs();
+        Decibel::new(-40.0 + 35.0 * syllables)
+    }
+}
+
+impl MeterSource for SimulatedMeterSource {
+    fn run(self: Box<Self>, store: &WingMeterStore, stop: &AtomicBool) -> Result<(), WingError> {
+        let start = Instant::now();
+        while !stop.load(Ordering::Relaxed) {
+            let t = start.elapsed().as_secs_f32();
+
+            for channel_idx in WingChannelId::MIN_ID..=WingChannelId::MAX_ID {
+                let channel_id = WingChannelId::new(channel_idx).unwrap();
+                store.set_channel(channel_id, Self::channel_level(channel_idx, t));
+            }
+            for dca_idx in WingDcaId::MIN_ID..=WingDcaId::MAX_ID {
+                let dca_id = WingDcaId::new(dca_idx).unwrap();
+                store.set_dca(dca_id, Self::channel_level(dca_idx, t) - 3.0);
+            }
+
+            std::thread::sleep(self.interval);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+
+    /// Lowest level the console reports
+    const SILENT: f32 = i16::MIN as f32 / 256.0;
+
+    /// Builds a report from `(value index, level in dB)` pairs, every other value is silent.
+    fn report(levels: &[(usize, f32)]) -> Vec<u8> {
+        let len = WingChannelId::MAX_ID as usize * CHANNEL_METER_VALUES
+            + WingDcaId::MAX_ID as usize * DCA_METER_VALUES;
+        let mut values = vec![i16::MIN; len];
+        for (idx, level) in levels {
+            values[*idx] = (level * 256.0) as i16;
+        }
+
+        let mut report = METER_REPORT_ID.to_be_bytes().to_vec();
+        for value in values {
+            report.extend(value.to_be_bytes());
+        }
+        report
+    }
+
+    fn dca_value(dca_idx: usize, value: usize) -> usize {
+        WingChannelId::MAX_ID as usize * CHANNEL_METER_VALUES + dca_idx * DCA_METER_VALUES + value
+    }
+
+    #[test]
+    fn parse_takes_louder_output_of_channel() {
+        let channel_3 = 2 * CHANNEL_METER_VALUES;
+        let report = report(&[
+            // Input and gain reduction values must be ignored
+            (channel_3, 0.0),
+            (channel_3 + CHANNEL_METER_OUTPUT, -20.0),
+            (channel_3 + CHANNEL_METER_OUTPUT + 1, -10.5),
+            (channel_3 + CHANNEL_METER_OUTPUT + 2, 0.0),
+        ]);
+
+        let store = WingMeterStore::default();
+        WingMeterSource::parse(&report, &store);
+
+        assert_eq!(
+            store.channel(WingChannelId::new(3).unwrap()).as_f32(),
+            -10.5
+        );
+        assert_eq!(
+            store.channel(WingChannelId::new(2).unwrap()).as_f32(),
+            SILENT
+        );
+        assert_eq!(
+            store.channel(WingChannelId::new(4).unwrap()).as_f32(),
+            SILENT
+        );
+    }
+
+    #[test]
+    fn parse_takes_post_fader_level_of_dca() {
+        let report = report(&[
+            (dca_value(0, 0), 0.0),
+            (dca_value(0, DCA_METER_POST), -6.0),
+            (dca_value(15, DCA_METER_POST), -12.25),
+        ]);
+
+        let store = WingMeterStore::default();
+        WingMeterSource::parse(&report, &store);
+
+        assert_eq!(store.dca(WingDcaId::new(1).unwrap()).as_f32(), -6.0);
+        assert_eq!(store.dca(WingDcaId::new(16).unwrap()).as_f32(), -12.25);
+        assert_eq!(
+            store
+                .channel(WingChannelId::new(WingChannelId::MAX_ID).unwrap())
+                .as_f32(),
+            SILENT
+        );
+    }
+
+    #[test]
+    fn parse_ignores_foreign_reports() {
+        let mut report = report(&[(CHANNEL_METER_OUTPUT, -3.0)]);
+        report[0] ^= 0xff;
+
+        let store = WingMeterStore::default();
+        WingMeterSource::parse(&report, &store);
+        WingMeterSource::parse(&report[..2], &store);
+
+        assert!(store
+            .channel(WingChannelId::new(1).unwrap())
+            .is_neg_infinity());
+    }
+
+    #[test]
+    fn simulated_source_fills_store_until_stopped() {
+        let store = Arc::new(WingMeterStore::default());
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let source = Box::new(SimulatedMeterSource::new(Duration::from_millis(1)));
+        let thread = std::thread::spawn({
+            let store = store.clone();
+            let stop = stop.clone();
+            move || source.run(&store, &stop)
+        });
+
+        std::thread::sleep(Duration::from_millis(20));
+        stop.store(true, Ordering::Relaxed);
+        thread.join().unwrap().unwrap();
+
+        // Channel 1 starts in the middle of a burst
+        let level = store.channel(WingChannelId::new(1).unwrap());
+        assert!(!level.is_neg_infinity());
+        assert!(level.as_f32() <= 0.0);
+    }
+}
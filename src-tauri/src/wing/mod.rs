@@ -1,15 +1,72 @@
 mod channel;
 pub use channel::*;
 
+mod strip;
+pub use strip::*;
+
 mod dca;
 pub use dca::*;
 
+mod mute_group;
+pub use mute_group::*;
+
+mod fx;
+pub use fx::*;
+
 pub mod error;
 
 mod console;
 pub use console::*;
 
+mod backend;
+pub use backend::*;
+
+mod sim;
+pub use sim::*;
+
+mod value;
+pub use value::*;
+
+mod cache;
+pub use cache::*;
+
+mod transaction;
+pub use transaction::*;
+
+mod traffic;
+pub use traffic::*;
+
+mod tree;
+pub use tree::*;
+
+mod fader;
+pub use fader::*;
+
+mod params;
+pub use params::*;
+
+mod eq;
+pub use eq::*;
+
+mod dynamics;
+pub use dynamics::*;
+
+mod send;
+pub use send::*;
+
+mod input;
+pub use input::*;
+
+mod delay;
+pub use delay::*;
+
 mod color;
 pub use color::*;
 
+mod discovery;
+pub use discovery::*;
+
+mod meter;
+pub use meter::*;
+
 pub mod id;
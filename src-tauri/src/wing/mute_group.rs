@@ -0,0 +1,84 @@
+use libwing::WingConsole;
+
+use crate::wing::{
+    error::WingError,
+    id::{deserialize_id, WingId},
+    MixerBackend,
+};
+
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, specta::Type,
+)]
+pub struct WingMuteGroupId(u8);
+
+impl WingId for WingMuteGroupId {
+    type Id = u8;
+
+    const MIN_ID: u8 = 1;
+    const MAX_ID: u8 = 8;
+
+    fn unchecked_new(id: u8) -> Self {
+        Self(id)
+    }
+
+    fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for WingMuteGroupId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_id(deserializer)
+    }
+}
+
+impl TryFrom<u8> for WingMuteGroupId {
+    type Error = WingError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+pub struct WingMuteGroup<'a> {
+    wing: &'a dyn MixerBackend,
+    id: WingMuteGroupId,
+}
+
+impl<'a> WingMuteGroup<'a> {
+    pub fn new(wing: &'a dyn MixerBackend, id: WingMuteGroupId) -> Self {
+        Self { wing, id }
+    }
+}
+
+impl<'a> WingMuteGroup<'a> {
+    fn get_mute_group_property(&self, property: &str) -> Result<i32, WingError> {
+        let name = format!("/mgrp/{}/{}", self.id.display(), property);
+        WingConsole::name_to_id(&name).ok_or(WingError::UnknownNodePath(name))
+    }
+
+    pub async fn is_muted(&self) -> Result<bool, WingError> {
+        let int_data = self
+            .wing
+            .request_int(self.get_mute_group_property("mute")?)
+            .await?;
+
+        Ok(int_data != 0)
+    }
+
+    pub fn mute(&self) -> Result<(), WingError> {
+        self.wing.set_int(self.get_mute_group_property("mute")?, 1)
+    }
+
+    pub fn unmute(&self) -> Result<(), WingError> {
+        self.wing.set_int(self.get_mute_group_property("mute")?, 0)
+    }
+
+    pub fn set_muted(&self, muted: bool) -> Result<(), WingError> {
+        if muted {
+            self.mute()
+        } else {
+            self.unmute()
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::wing::{error::WingError, WingChannel, WingValue};
+
+/// Relative tolerance for comparing float parameters. The console quantizes most values, so a
+/// value read back is rarely exactly the value that was written.
+const FLOAT_TOLERANCE: f32 = 0.005;
+
+/// A parameter that differs between two states of a processing block.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WingParameterDifference {
+    /// Property path relative to the strip, e.g. `eq/1g`
+    pub parameter: String,
+    pub from: WingValue,
+    pub to: WingValue,
+}
+
+/// A block of strip parameters (EQ, gate, ...) that is read and written as a whole.
+pub trait WingParameterBlock {
+    /// All parameters of the block as property paths relative to the strip, in a fixed order.
+    fn parameters(&self) -> Vec<(String, WingValue)>;
+
+    /// Lists all parameters that differ from `other`, ignoring the console's rounding.
+    fn diff(&self, other: &Self) -> Vec<WingParameterDifference> {
+        self.parameters()
+            .into_iter()
+            .zip(other.parameters())
+            .filter(|((_, from), (_, to))| !values_match(from, to))
+            .map(|((parameter, from), (_, to))| WingParameterDifference {
+                parameter,
+                from,
+                to,
+            })
+            .collect()
+    }
+
+    fn matches(&self, other: &Self) -> bool {
+        self.diff(other).is_empty()
+    }
+
+    fn write(&self, channel: &WingChannel) -> Result<(), WingError> {
+        for (parameter, value) in self.parameters() {
+            channel.set_value(&parameter, &value)?;
+        }
+        Ok(())
+    }
+}
+
+fn values_match(a: &WingValue, b: &WingValue) -> bool {
+    match (a, b) {
+        (WingValue::Float(a), WingValue::Float(b)) => {
+            (a - b).abs() <= a.abs().max(b.abs()).max(1.0) * FLOAT_TOLERANCE
+        }
+        (a, b) => a == b,
+    }
+}
+
+pub(super) fn bool_value(value: bool) -> WingValue {
+    WingValue::Int(value as i32)
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use libwing::WingConsole;
+
+    use super::*;
+
+    /// Checks that every parameter of `block` exists on the strip and is a node of the type that
+    /// is written to it.
+    pub fn assert_parameters_fit(channel: &WingChannel, block: &impl WingParameterBlock) {
+        for (parameter, value) in block.parameters() {
+            let path = format!("{}/{}", channel.id().path(), parameter);
+            let defs = WingConsole::name_to_id(&path)
+                .and_then(WingConsole::id_to_defs)
+                .unwrap_or_default();
+            let Some((_, def)) = defs.first() else {
+                panic!("{} does not resolve", path);
+            };
+
+            let node_type = format!("{:?}", def.node_type);
+            let fits = match value {
+                WingValue::Float(_) => node_type.contains("Float") || node_type.contains("Fader"),
+                WingValue::Int(_) => node_type.contains("Int"),
+                WingValue::String(_) => node_type.contains("String"),
+            };
+            assert!(fits, "{} is a {} node, not a {:?}", path, node_type, value);
+        }
+    }
+}
@@ -0,0 +1,268 @@
+use crate::{
+    utils::Decibel,
+    wing::{
+        db_to_fader,
+        error::WingError,
+        fader_to_db,
+        id::{deserialize_id, WingId},
+        params::bool_value,
+        WingChannel, WingValue,
+    },
+};
+
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, specta::Type,
+)]
+pub struct WingBusId(u8);
+
+impl WingId for WingBusId {
+    type Id = u8;
+
+    const MIN_ID: u8 = 1;
+    const MAX_ID: u8 = 16;
+
+    fn unchecked_new(id: u8) -> Self {
+        Self(id)
+    }
+
+    fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for WingBusId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_id(deserializer)
+    }
+}
+
+impl TryFrom<u8> for WingBusId {
+    type Error = WingError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, specta::Type,
+)]
+pub struct WingMatrixId(u8);
+
+impl WingId for WingMatrixId {
+    type Id = u8;
+
+    const MIN_ID: u8 = 1;
+    const MAX_ID: u8 = 8;
+
+    fn unchecked_new(id: u8) -> Self {
+        Self(id)
+    }
+
+    fn value(&self) -> u8 {
+        self.0
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for WingMatrixId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_id(deserializer)
+    }
+}
+
+impl TryFrom<u8> for WingMatrixId {
+    type Error = WingError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+/// What a channel sends to, either a bus or a matrix.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    specta::Type,
+)]
+pub enum WingSendTarget {
+    Bus(WingBusId),
+    Matrix(WingMatrixId),
+}
+
+impl WingSendTarget {
+    /// Send node name, e.g. `3` for bus 3 and `MX2` for matrix 2
+    fn node(&self) -> String {
+        match self {
+            Self::Bus(bus) => bus.display().to_string(),
+            Self::Matrix(matrix) => format!("MX{}", matrix.display()),
+        }
+    }
+}
+
+impl From<WingBusId> for WingSendTarget {
+    fn from(value: WingBusId) -> Self {
+        Self::Bus(value)
+    }
+}
+
+impl From<WingMatrixId> for WingSendTarget {
+    fn from(value: WingMatrixId) -> Self {
+        Self::Matrix(value)
+    }
+}
+
+/// Where in the channel the signal for a send is taken from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum WingSendTap {
+    /// Before the channel fader, e.g. for monitor mixes
+    Pre,
+    /// After the channel fader, e.g. for effects
+    Post,
+}
+
+impl WingSendTap {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pre => "PRE",
+            Self::Post => "POST",
+        }
+    }
+
+    fn from_value(value: &str) -> Self {
+        match value {
+            "PRE" => Self::Pre,
+            _ => Self::Post,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WingSend {
+    pub on: bool,
+    pub level: Decibel,
+    pub tap: WingSendTap,
+}
+
+// Sends
+impl<'a> WingChannel<'a> {
+    fn send_property(target: impl Into<WingSendTarget>, property: &str) -> String {
+        format!("send/{}/{}", target.into().node(), property)
+    }
+
+    pub async fn get_send_level(
+        &self,
+        target: impl Into<WingSendTarget>,
+    ) -> Result<Decibel, WingError> {
+        let value = self.get_float(&Self::send_property(target, "lvl")).await?;
+        Ok(fader_to_db(value))
+    }
+
+    pub fn set_send_level(
+        &self,
+        target: impl Into<WingSendTarget>,
+        level: Decibel,
+    ) -> Result<(), WingError> {
+        self.set_value(
+            &Self::send_property(target, "lvl"),
+            &WingValue::Float(db_to_fader(level)),
+        )
+    }
+
+    pub async fn is_send_on(&self, target: impl Into<WingSendTarget>) -> Result<bool, WingError> {
+        self.get_bool(&Self::send_property(target, "on")).await
+    }
+
+    pub fn set_send_on(
+        &self,
+        target: impl Into<WingSendTarget>,
+        on: bool,
+    ) -> Result<(), WingError> {
+        self.set_value(&Self::send_property(target, "on"), &bool_value(on))
+    }
+
+    pub async fn get_send_tap(
+        &self,
+        target: impl Into<WingSendTarget>,
+    ) -> Result<WingSendTap, WingError> {
+        let tap = self
+            .get_string(&Self::send_property(target, "mode"))
+            .await?;
+        Ok(WingSendTap::from_value(&tap))
+    }
+
+    pub fn set_send_tap(
+        &self,
+        target: impl Into<WingSendTarget>,
+        tap: WingSendTap,
+    ) -> Result<(), WingError> {
+        self.set_value(
+            &Self::send_property(target, "mode"),
+            &WingValue::String(tap.as_str().to_string()),
+        )
+    }
+
+    pub async fn get_send(&self, target: impl Into<WingSendTarget>) -> Result<WingSend, WingError> {
+        let target = target.into();
+        Ok(WingSend {
+            on: self.is_send_on(target).await?,
+            level: self.get_send_level(target).await?,
+            tap: self.get_send_tap(target).await?,
+        })
+    }
+
+    pub fn set_send(
+        &self,
+        target: impl Into<WingSendTarget>,
+        send: &WingSend,
+    ) -> Result<(), WingError> {
+        let target = target.into();
+        self.set_send_on(target, send.on)?;
+        self.set_send_level(target, send.level)?;
+        self.set_send_tap(target, send.tap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wing::{MixerBackend, SimulatedWing, WingChannelId};
+
+    #[test]
+    fn send_levels_are_stored_in_db() {
+        let wing = SimulatedWing::default();
+        let backend: &dyn MixerBackend = &wing;
+        let channel = backend.channel(WingChannelId::new(1).unwrap());
+        let bus = WingBusId::new(2).unwrap();
+        let matrix = WingMatrixId::new(1).unwrap();
+
+        let send = WingSend {
+            on: true,
+            level: Decibel::new(-6.0),
+            tap: WingSendTap::Pre,
+        };
+        channel.set_send(bus, &send).unwrap();
+        channel
+            .set_send_level(matrix, Decibel::NEG_INFINITY)
+            .unwrap();
+
+        assert_eq!(
+            wing.value_at("/ch/1/send/2/lvl"),
+            Some(WingValue::Float(-6.0))
+        );
+        assert_eq!(
+            wing.value_at("/ch/1/send/MX1/lvl"),
+            Some(WingValue::Float(Decibel::MIN))
+        );
+        let read = tauri::async_runtime::block_on(channel.get_send(bus)).unwrap();
+        assert_eq!(read, send);
+    }
+}
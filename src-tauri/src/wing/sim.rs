@@ -0,0 +1,111 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use libwing::{WingConsole, WingNodeDef};
+
+use crate::wing::{
+    error::WingError, BackendFuture, CachedValue, MixerBackend, WingTraffic, WingTrafficDirection,
+    WingTrafficListener, WingValue,
+};
+
+/// An in-memory stand-in for a Wing console.
+///
+/// Node values are kept in a map keyed by node ID. Nodes that have never been written read
+/// as an empty string, `0` or `0.0` respectively. The simulator has no node tree, so node
+/// definitions are never returned.
+#[derive(Default)]
+pub struct SimulatedWing {
+    nodes: Mutex<HashMap<i32, WingValue>>,
+    traffic_listener: RwLock<Option<WingTrafficListener>>,
+}
+
+impl SimulatedWing {
+    pub fn value(&self, node_id: i32) -> Option<WingValue> {
+        self.nodes.lock().unwrap().get(&node_id).cloned()
+    }
+
+    pub fn value_at(&self, path: &str) -> Option<WingValue> {
+        WingConsole::name_to_id(path).and_then(|node_id| self.value(node_id))
+    }
+
+    pub fn set_value(&self, node_id: i32, value: WingValue) {
+        if let Some(listener) = self.traffic_listener.read().unwrap().as_ref() {
+            listener(WingTraffic::new(
+                WingTrafficDirection::Sent,
+                node_id,
+                value.to_string(),
+            ));
+        }
+
+        self.nodes.lock().unwrap().insert(node_id, value);
+    }
+
+    fn read<T: Send + 'static>(
+        &self,
+        node_id: i32,
+        f: impl FnOnce(&WingValue) -> T,
+        default: T,
+    ) -> BackendFuture<'_, T> {
+        let value = self
+            .value(node_id)
+            .map(|value| f(&value))
+            .unwrap_or(default);
+        Box::pin(async move { Ok(value) })
+    }
+}
+
+impl MixerBackend for SimulatedWing {
+    fn request_string(&self, node_id: i32) -> BackendFuture<'_, String> {
+        self.read(node_id, WingValue::as_string, String::new())
+    }
+
+    fn request_int(&self, node_id: i32) -> BackendFuture<'_, i32> {
+        self.read(node_id, WingValue::as_int, 0)
+    }
+
+    fn request_float(&self, node_id: i32) -> BackendFuture<'_, f32> {
+        self.read(node_id, WingValue::as_float, 0.0)
+    }
+
+    fn request_node_children(&self, _node_id: i32) -> BackendFuture<'_, Vec<Arc<WingNodeDef>>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    fn set_string(&self, node_id: i32, value: &str) -> Result<(), WingError> {
+        self.set_value(node_id, WingValue::String(value.to_string()));
+        Ok(())
+    }
+
+    fn set_int(&self, node_id: i32, value: i32) -> Result<(), WingError> {
+        self.set_value(node_id, WingValue::Int(value));
+        Ok(())
+    }
+
+    fn set_float(&self, node_id: i32, value: f32) -> Result<(), WingError> {
+        self.set_value(node_id, WingValue::Float(value));
+        Ok(())
+    }
+
+    fn cached_value(&self, node_id: i32) -> Option<CachedValue> {
+        self.value(node_id).map(CachedValue::Written)
+    }
+
+    fn write_batch(&self, writes: Vec<(i32, WingValue)>) -> Result<(), WingError> {
+        for (node_id, value) in writes {
+            self.set_value(node_id, value);
+        }
+        Ok(())
+    }
+
+    fn set_traffic_listener(&self, listener: Option<WingTrafficListener>) {
+        *self.traffic_listener.write().unwrap() = listener;
+    }
+
+    fn ping(&self) -> BackendFuture<'_, ()> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn close(&self) {}
+}
@@ -0,0 +1,123 @@
+use std::ops::RangeInclusive;
+
+use crate::wing::{error::WingError, id::WingId, WingChannelId};
+
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde::Serialize,
+    serde::Deserialize,
+    specta::Type,
+)]
+pub enum WingStripKind {
+    Channel,
+    Aux,
+    Bus,
+    Main,
+    Matrix,
+}
+
+impl WingStripKind {
+    pub fn path_prefix(&self) -> &'static str {
+        match self {
+            Self::Channel => "ch",
+            Self::Aux => "aux",
+            Self::Bus => "bus",
+            Self::Main => "main",
+            Self::Matrix => "mtx",
+        }
+    }
+
+    pub fn id_range(&self) -> RangeInclusive<u8> {
+        match self {
+            Self::Channel => WingChannelId::MIN_ID..=WingChannelId::MAX_ID,
+            Self::Aux => 1..=8,
+            Self::Bus => 1..=16,
+            Self::Main => 1..=4,
+            Self::Matrix => 1..=8,
+        }
+    }
+
+    /// Whether strips of this kind are fed by an input source (and can therefore be source
+    /// linked).
+    pub fn has_source(&self) -> bool {
+        matches!(self, Self::Channel | Self::Aux)
+    }
+}
+
+/// Any strip on the console: an input channel, aux, bus, main or matrix.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WingStripId {
+    kind: WingStripKind,
+    number: u8,
+}
+
+impl WingStripId {
+    pub fn new(kind: WingStripKind, number: u8) -> Result<Self, WingError> {
+        let range = kind.id_range();
+        if range.contains(&number) {
+            Ok(Self { kind, number })
+        } else {
+            Err(WingError::id_out_of_bounds(
+                number,
+                *range.start(),
+                *range.end(),
+            ))
+        }
+    }
+
+    pub fn kind(&self) -> WingStripKind {
+        self.kind
+    }
+
+    pub fn number(&self) -> u8 {
+        self.number
+    }
+
+    /// Path of the strip node, e.g. `/ch/12`
+    pub fn path(&self) -> String {
+        format!("/{}/{}", self.kind.path_prefix(), self.number)
+    }
+}
+
+impl std::fmt::Display for WingStripId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} {}", self.kind, self.number)
+    }
+}
+
+impl From<WingChannelId> for WingStripId {
+    fn from(value: WingChannelId) -> Self {
+        Self {
+            kind: WingStripKind::Channel,
+            number: value.value(),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for WingStripId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            /// Show files from before strips were introduced only stored channel numbers
+            Channel(u8),
+            #[serde(rename_all = "camelCase")]
+            Strip { kind: WingStripKind, number: u8 },
+        }
+
+        let (kind, number) = match Repr::deserialize(deserializer)? {
+            Repr::Channel(number) => (WingStripKind::Channel, number),
+            Repr::Strip { kind, number } => (kind, number),
+        };
+
+        Self::new(kind, number).map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use libwing::WingConsole;
+
+#[derive(Copy, Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum WingTrafficDirection {
+    Sent,
+    Received,
+}
+
+/// A single parameter sent to or received from the console.
+#[derive(Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WingTraffic {
+    pub direction: WingTrafficDirection,
+    pub node_id: i32,
+    pub path: String,
+    pub value: String,
+}
+
+impl WingTraffic {
+    pub fn new(direction: WingTrafficDirection, node_id: i32, value: String) -> Self {
+        Self {
+            direction,
+            node_id,
+            path: node_path(node_id),
+            value,
+        }
+    }
+}
+
+pub type WingTrafficListener = Arc<dyn Fn(WingTraffic) + Send + Sync>;
+
+/// Resolves a node ID to a human readable property path.
+pub fn node_path(node_id: i32) -> String {
+    match WingConsole::id_to_defs(node_id) {
+        Some(defs) if defs.len() == 1 => defs[0].0.clone(),
+        Some(defs) if defs.len() > 1 => {
+            let u =
+                std::collections::HashSet::<u16>::from_iter(defs.iter().map(|(_, def)| def.index));
+            if u.len() == 1 {
+                // check out propmap.jsonl for more info on the property
+                String::from("prop") + defs[0].1.index.to_string().as_str()
+            } else {
+                format!("<MultiProp:{}>", node_id)
+            }
+        }
+        _ => format!("<Unknown:{}>", node_id),
+    }
+}
@@ -0,0 +1,124 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use libwing::WingNodeDef;
+
+use crate::wing::{
+    error::WingError, BackendFuture, CachedValue, MixerBackend, WingTrafficListener, WingValue,
+};
+
+/// Collects the intended end state of a set of nodes and sends only what actually changes.
+///
+/// A transaction is a [`MixerBackend`] itself, so all wrappers can be used on it. Writes are
+/// staged (the last write to a node wins) and reads see the staged values. Nothing is sent
+/// until [`WingTransaction::commit`] is called.
+pub struct WingTransaction<'a> {
+    backend: &'a dyn MixerBackend,
+    writes: Mutex<BTreeMap<i32, WingValue>>,
+}
+
+impl<'a> WingTransaction<'a> {
+    pub fn new(backend: &'a dyn MixerBackend) -> Self {
+        Self {
+            backend,
+            writes: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    fn staged(&self, node_id: i32) -> Option<WingValue> {
+        self.writes.lock().unwrap().get(&node_id).cloned()
+    }
+
+    fn stage(&self, node_id: i32, value: WingValue) {
+        self.writes.lock().unwrap().insert(node_id, value);
+    }
+
+    /// Drops all writes that match the known console state and sends the rest in one batch.
+    /// Returns the number of values that were sent.
+    pub fn commit(self) -> Result<usize, WingError> {
+        let backend = self.backend;
+        let writes = self
+            .writes
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .filter(|(node_id, value)| {
+                !backend
+                    .cached_value(*node_id)
+                    .is_some_and(|cached| cached.matches(value))
+            })
+            .collect::<Vec<_>>();
+
+        let count = writes.len();
+        if count > 0 {
+            backend.write_batch(writes)?;
+        }
+
+        Ok(count)
+    }
+}
+
+impl MixerBackend for WingTransaction<'_> {
+    fn request_string(&self, node_id: i32) -> BackendFuture<'_, String> {
+        match self.staged(node_id) {
+            Some(value) => Box::pin(async move { Ok(value.as_string()) }),
+            None => self.backend.request_string(node_id),
+        }
+    }
+
+    fn request_int(&self, node_id: i32) -> BackendFuture<'_, i32> {
+        match self.staged(node_id) {
+            Some(value) => Box::pin(async move { Ok(value.as_int()) }),
+            None => self.backend.request_int(node_id),
+        }
+    }
+
+    fn request_float(&self, node_id: i32) -> BackendFuture<'_, f32> {
+        match self.staged(node_id) {
+            Some(value) => Box::pin(async move { Ok(value.as_float()) }),
+            None => self.backend.request_float(node_id),
+        }
+    }
+
+    fn request_node_children(&self, node_id: i32) -> BackendFuture<'_, Vec<Arc<WingNodeDef>>> {
+        self.backend.request_node_children(node_id)
+    }
+
+    fn set_string(&self, node_id: i32, value: &str) -> Result<(), WingError> {
+        self.stage(node_id, WingValue::String(value.to_string()));
+        Ok(())
+    }
+
+    fn set_int(&self, node_id: i32, value: i32) -> Result<(), WingError> {
+        self.stage(node_id, WingValue::Int(value));
+        Ok(())
+    }
+
+    fn set_float(&self, node_id: i32, value: f32) -> Result<(), WingError> {
+        self.stage(node_id, WingValue::Float(value));
+        Ok(())
+    }
+
+    fn cached_value(&self, node_id: i32) -> Option<CachedValue> {
+        self.staged(node_id)
+            .map(CachedValue::Written)
+            .or_else(|| self.backend.cached_value(node_id))
+    }
+
+    fn write_batch(&self, writes: Vec<(i32, WingValue)>) -> Result<(), WingError> {
+        self.writes.lock().unwrap().extend(writes);
+        Ok(())
+    }
+
+    fn set_traffic_listener(&self, listener: Option<WingTrafficListener>) {
+        self.backend.set_traffic_listener(listener);
+    }
+
+    fn ping(&self) -> BackendFuture<'_, ()> {
+        self.backend.ping()
+    }
+
+    fn close(&self) {}
+}
@@ -0,0 +1,101 @@
+use libwing::{WingConsole, WingNodeDef};
+
+use crate::wing::{error::WingError, BackendFuture, MixerBackend};
+
+/// A node of the console parameter tree, together with its current value.
+#[derive(Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "camelCase")]
+pub struct WingNodeInfo {
+    pub id: i32,
+    pub path: String,
+    pub name: String,
+    pub long_name: String,
+    pub node_type: String,
+    pub unit: String,
+    pub read_only: bool,
+    /// Allowed value range, if the node has one
+    pub range: Option<(f32, f32)>,
+    /// Current value, only set for parameters (i.e. nodes without children)
+    pub value: Option<String>,
+    /// `None` if the children have not been walked (because of the depth limit)
+    pub children: Option<Vec<WingNodeInfo>>,
+}
+
+impl WingNodeInfo {
+    fn new(path: String, def: Option<&WingNodeDef>, node_id: i32) -> Self {
+        let range = def.and_then(|def| {
+            if def.min_float != def.max_float {
+                Some((def.min_float, def.max_float))
+            } else if def.min_int != def.max_int {
+                Some((def.min_int as f32, def.max_int as f32))
+            } else {
+                None
+            }
+        });
+
+        Self {
+            id: node_id,
+            path,
+            name: def.map(|def| def.name.clone()).unwrap_or_default(),
+            long_name: def.map(|def| def.long_name.clone()).unwrap_or_default(),
+            node_type: def
+                .map(|def| format!("{:?}", def.node_type))
+                .unwrap_or_default(),
+            unit: def.map(|def| format!("{:?}", def.unit)).unwrap_or_default(),
+            read_only: def.is_some_and(|def| def.read_only),
+            range,
+            value: None,
+            children: None,
+        }
+    }
+}
+
+/// Resolves a node path (e.g. `/ch/1/eq`) to its node ID. The root is `/` or an empty path.
+pub fn node_id_from_path(path: &str) -> Result<i32, WingError> {
+    let path = path.trim_end_matches('/');
+    if path.is_empty() {
+        return Ok(0);
+    }
+
+    WingConsole::name_to_id(path).ok_or_else(|| WingError::UnknownNodePath(path.to_string()))
+}
+
+impl dyn MixerBackend + '_ {
+    /// Walks the parameter tree starting at `path`, descending at most `depth` levels.
+    pub async fn browse(&self, path: &str, depth: u32) -> Result<WingNodeInfo, WingError> {
+        let node_id = node_id_from_path(path)?;
+        let path = path.trim_end_matches('/').to_string();
+
+        let defs = WingConsole::id_to_defs(node_id).unwrap_or_default();
+        let def = defs.first().map(|(_, def)| def);
+
+        self.walk(WingNodeInfo::new(path, def, node_id), depth)
+            .await
+    }
+
+    fn walk(&self, mut node: WingNodeInfo, depth: u32) -> BackendFuture<'_, WingNodeInfo> {
+        Box::pin(async move {
+            let children = self.request_node_children(node.id).await?;
+
+            if children.is_empty() {
+                node.value = Some(self.request_string(node.id).await?);
+                node.children = Some(Vec::new());
+                return Ok(node);
+            }
+
+            if depth == 0 {
+                return Ok(node);
+            }
+
+            let mut walked = Vec::with_capacity(children.len());
+            for child in children {
+                let path = format!("{}/{}", node.path, child.name);
+                let child = WingNodeInfo::new(path, Some(&child), child.id);
+                walked.push(self.walk(child, depth - 1).await?);
+            }
+
+            node.children = Some(walked);
+            Ok(node)
+        })
+    }
+}
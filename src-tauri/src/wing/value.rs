@@ -0,0 +1,43 @@
+/// A single node value as it is sent to or read from the console.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub enum WingValue {
+    String(String),
+    Int(i32),
+    Float(f32),
+}
+
+impl WingValue {
+    pub fn as_string(&self) -> String {
+        match self {
+            Self::String(value) => value.clone(),
+            Self::Int(value) => value.to_string(),
+            Self::Float(value) => value.to_string(),
+        }
+    }
+
+    pub fn as_int(&self) -> i32 {
+        match self {
+            Self::String(value) => value.parse().unwrap_or_default(),
+            Self::Int(value) => *value,
+            Self::Float(value) => value.round() as i32,
+        }
+    }
+
+    pub fn as_float(&self) -> f32 {
+        match self {
+            Self::String(value) => value.parse().unwrap_or_default(),
+            Self::Int(value) => *value as f32,
+            Self::Float(value) => *value,
+        }
+    }
+}
+
+impl std::fmt::Display for WingValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::String(value) => value.fmt(f),
+            Self::Int(value) => value.fmt(f),
+            Self::Float(value) => value.fmt(f),
+        }
+    }
+}